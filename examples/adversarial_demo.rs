@@ -1,9 +1,9 @@
-/// Adversarial Middleware Demonstration
-///
-/// This example demonstrates chaos injection and security fuzzing middleware
-/// in action. Run with: cargo run --example adversarial_demo
-///
-/// WARNING: These middleware are for TESTING ONLY!
+//! Adversarial Middleware Demonstration
+//!
+//! This example demonstrates chaos injection and security fuzzing middleware
+//! in action. Run with: cargo run --example adversarial_demo
+//!
+//! WARNING: These middleware are for TESTING ONLY!
 
 use event_chains::core::event_chain::EventChain;
 use event_chains::core::event_context::EventContext;
@@ -14,6 +14,7 @@ use event_chains::middleware::logging::LoggingMiddleware;
 use event_chains::middleware::retry::RetryMiddleware;
 use event_chains::middleware::metrics::MetricsMiddleware;
 use event_chains::middleware::chaos::{ChaosMiddleware, ChaosType, ChaosConfig};
+use event_chains::middleware::fuzz_oracle::ExpectRejectionOracle;
 use event_chains::middleware::fuzzing::{FuzzingMiddleware, FuzzType, FuzzConfig};
 
 // ============================================================================
@@ -137,6 +138,7 @@ fn test_chaos_injection() {
         ],
         min_latency_ms: 10,
         max_latency_ms: 100,
+        ..Default::default()
     });
 
     let metrics = MetricsMiddleware::new();
@@ -205,7 +207,12 @@ fn test_security_fuzzing() {
             "filename".to_string(),
             "input".to_string(),
         ],
-    });
+        // Keep the payloads in the context so they can be inspected below
+        restore_context: false,
+        ..Default::default()
+    })
+    // These events validate their input, so accepting a payload is a finding
+    .with_oracle(ExpectRejectionOracle::all());
 
     let chain = EventChain::new()
         .middleware(fuzzer.clone())
@@ -412,7 +419,7 @@ fn test_besteffort_semantics() {
     println!("      Failures: {}", result.failures.len());
     println!("      Status: {:?}", result.status);
     println!("      Is middleware failure: {}",
-             result.failures.get(0).map(|f| f.is_middleware_failure).unwrap_or(false));
+             result.failures.first().map(|f| f.is_middleware_failure).unwrap_or(false));
     println!("      [+] BestEffort stopped immediately on infrastructure failure\n");
 
     println!(" Result: BestEffort mode semantics validated!");
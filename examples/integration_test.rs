@@ -1,7 +1,7 @@
-/// Integration test: All middleware and fault tolerance modes
-///
-/// This test verifies that all middleware works correctly together
-/// and that fault tolerance modes behave as expected.
+//! Integration test: All middleware and fault tolerance modes
+//!
+//! This test verifies that all middleware works correctly together
+//! and that fault tolerance modes behave as expected.
 
 use event_chains::core::event_chain::EventChain;
 use event_chains::core::event_context::EventContext;
@@ -5,6 +5,10 @@ use crate::core::event_failure::EventFailure;
 pub struct ChainResult {
     pub success: bool,
     pub failures: Vec<EventFailure>,
+    /// Original failures of steps that were recovered by a fallback event
+    pub fallbacks: Vec<EventFailure>,
+    /// Non-fatal problems reported by middleware, e.g. exceeded time budgets
+    pub warnings: Vec<EventFailure>,
     pub status: ChainStatus,
 }
 
@@ -20,6 +24,8 @@ impl ChainResult {
         Self {
             success: true,
             failures: Vec::new(),
+            fallbacks: Vec::new(),
+            warnings: Vec::new(),
             status: ChainStatus::Completed,
         }
     }
@@ -28,6 +34,8 @@ impl ChainResult {
         Self {
             success: true,
             failures,
+            fallbacks: Vec::new(),
+            warnings: Vec::new(),
             status: ChainStatus::CompletedWithWarnings,
         }
     }
@@ -36,7 +44,36 @@ impl ChainResult {
         Self {
             success: false,
             failures,
+            fallbacks: Vec::new(),
+            warnings: Vec::new(),
             status: ChainStatus::Failed,
         }
     }
+
+    /// Attach the original failures of steps recovered by fallback events
+    pub fn with_fallbacks(mut self, fallbacks: Vec<EventFailure>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    /// Attach warnings reported during execution
+    ///
+    /// A completed chain with warnings becomes [`ChainStatus::CompletedWithWarnings`].
+    pub fn with_warnings(mut self, warnings: Vec<EventFailure>) -> Self {
+        if !warnings.is_empty() && self.status == ChainStatus::Completed {
+            self.status = ChainStatus::CompletedWithWarnings;
+        }
+        self.warnings = warnings;
+        self
+    }
+
+    /// Whether any warnings were reported
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Whether any step was completed by a fallback event
+    pub fn used_fallback(&self) -> bool {
+        !self.fallbacks.is_empty()
+    }
 }
@@ -7,6 +7,48 @@ use crate::core::fault_tolerance_mode::FaultToleranceMode;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
 
+/// Context key holding the original error message while a fallback event runs
+pub const FALLBACK_ERROR_KEY: &str = "fallback:error";
+
+/// Context key holding the name of the failed event while a fallback event runs
+pub const FALLBACK_EVENT_KEY: &str = "fallback:event";
+
+/// One chain execution on a context, ended even if an event panics
+struct ChainRun<'a> {
+    context: &'a mut EventContext,
+    outer_warnings: Option<Vec<EventFailure>>,
+}
+
+impl<'a> ChainRun<'a> {
+    fn begin(context: &'a mut EventContext) -> Self {
+        let outer_warnings = Some(context.begin_chain());
+        Self {
+            context,
+            outer_warnings,
+        }
+    }
+
+    /// End the execution and return its warnings
+    fn finish(mut self) -> Vec<EventFailure> {
+        let outer_warnings = self.outer_warnings.take().unwrap_or_default();
+        self.context.end_chain(outer_warnings)
+    }
+}
+
+impl Drop for ChainRun<'_> {
+    fn drop(&mut self) {
+        if let Some(outer_warnings) = self.outer_warnings.take() {
+            self.context.end_chain(outer_warnings);
+        }
+    }
+}
+
+/// An event together with its optional fallback
+struct ChainStep {
+    event: Box<dyn ChainableEvent>,
+    fallback: Option<Box<dyn ChainableEvent>>,
+}
+
 /// Main EventChain orchestrator
 ///
 /// Manages and executes a pipeline of events with optional middleware.
@@ -22,6 +64,12 @@ use crate::events::event_middleware::EventMiddleware;
 /// * **Lenient**: Continue on all failures, collect for review
 /// * **BestEffort**: Continue on event failures, but stop on middleware failures
 ///
+/// # Fallback Events
+///
+/// An event registered with [`event_with_fallback`](Self::event_with_fallback)
+/// has a substitute that runs when it fails, e.g. to serve cached data when a
+/// circuit breaker is open. See that method for details.
+///
 /// # Example
 ///
 /// ```ignore
@@ -40,7 +88,7 @@ use crate::events::event_middleware::EventMiddleware;
 /// let result = chain.execute(&mut context);
 /// ```
 pub struct EventChain {
-    events: Vec<Box<dyn ChainableEvent>>,
+    events: Vec<ChainStep>,
     middlewares: Vec<Box<dyn EventMiddleware>>,
     fault_tolerance: FaultToleranceMode,
 }
@@ -104,7 +152,52 @@ impl EventChain {
     ///
     /// * `E` - Any type implementing [`ChainableEvent`] + `'static`
     pub fn event<E: ChainableEvent + 'static>(mut self, event: E) -> Self {
-        self.events.push(Box::new(event));
+        self.events.push(ChainStep {
+            event: Box::new(event),
+            fallback: None,
+        });
+        self
+    }
+
+    /// Add an event with a fallback that runs in its place if it fails
+    ///
+    /// When `event` returns `EventResult::Failure` (including rejections by
+    /// circuit breaker, rate limit or retry middleware), `fallback` is executed
+    /// with the same context. While it runs, the original error is available
+    /// under [`FALLBACK_ERROR_KEY`] and the failed event's name under
+    /// [`FALLBACK_EVENT_KEY`]; both keys are removed afterwards.
+    ///
+    /// * If the fallback succeeds, the step does not count as a failure, but the
+    ///   original failure is recorded in [`ChainResult::fallbacks`] and the chain
+    ///   completes with [`ChainStatus::CompletedWithWarnings`].
+    /// * If the fallback fails, the step fails with both error messages and the
+    ///   fault tolerance mode applies as usual.
+    ///
+    /// The fallback runs directly, **without** the middleware stack, so it is not
+    /// rejected by the same circuit breaker that rejected the original event.
+    /// Middleware failures are infrastructure problems and never trigger a fallback.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let chain = EventChain::new()
+    ///     .middleware(CircuitBreakerMiddleware::new())
+    ///     .event_with_fallback(FetchPrices, CachedPrices);
+    ///
+    /// let result = chain.execute(&mut context);
+    /// if result.used_fallback() {
+    ///     println!("Served stale prices");
+    /// }
+    /// ```
+    pub fn event_with_fallback<E, F>(mut self, event: E, fallback: F) -> Self
+    where
+        E: ChainableEvent + 'static,
+        F: ChainableEvent + 'static,
+    {
+        self.events.push(ChainStep {
+            event: Box::new(event),
+            fallback: Some(Box::new(fallback)),
+        });
         self
     }
 
@@ -148,7 +241,10 @@ impl EventChain {
     /// Events execute in the order they are added (FIFO).
     /// See [`event()`](Self::event) for the recommended fluent API.
     pub fn add_event(&mut self, event: Box<dyn ChainableEvent>) -> &mut Self {
-        self.events.push(event);
+        self.events.push(ChainStep {
+            event,
+            fallback: None,
+        });
         self
     }
 
@@ -174,6 +270,11 @@ impl EventChain {
     /// * **Lenient**: Continues on all failures, collects them for review
     /// * **BestEffort**: Continues on event failures, stops on middleware failures
     ///
+    /// Warnings recorded with [`EventContext::add_warning`] never stop the chain;
+    /// they are returned in [`ChainResult::warnings`]. Warnings already in the
+    /// context, e.g. those of an outer chain whose event runs this one, are
+    /// kept out of the result and left in the context.
+    ///
     /// # Returns
     ///
     /// [`ChainResult`] containing success status and any failures that occurred
@@ -191,19 +292,55 @@ impl EventChain {
     /// }
     /// ```
     pub fn execute(&self, context: &mut EventContext) -> ChainResult {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("event_chain", events = self.events.len()).entered();
+
+        let run = ChainRun::begin(context);
+        let result = self.execute_steps(run.context);
+        result.with_warnings(run.finish())
+    }
+
+    fn execute_steps(&self, context: &mut EventContext) -> ChainResult {
         let mut failures = Vec::new();
+        let mut fallbacks = Vec::new();
+
+        for step in &self.events {
+            let event = &step.event;
 
-        for event in &self.events {
             // Build middleware pipeline (LIFO - last registered executes first)
             let result = self.execute_with_middleware(event.as_ref(), context);
 
             if result.is_failure() {
                 // Determine if this is a middleware or event failure
-                let (is_middleware_failure, error_msg) = match result.get_failure_info() {
+                let (is_middleware_failure, mut error_msg) = match result.get_failure_info() {
                     Some((is_mw, msg)) => (is_mw, msg.to_string()),
                     None => (false, "Unknown error".to_string()),
                 };
 
+                // Event failures may be recovered by a fallback
+                if !is_middleware_failure && let Some(fallback) = &step.fallback {
+                    context.set(FALLBACK_ERROR_KEY, error_msg.clone());
+                    context.set(FALLBACK_EVENT_KEY, event.name().to_string());
+                    let fallback_result = fallback.execute(context);
+                    context.remove(FALLBACK_ERROR_KEY);
+                    context.remove(FALLBACK_EVENT_KEY);
+
+                    match fallback_result.get_error() {
+                        None => {
+                            fallbacks.push(EventFailure::new(event.name().to_string(), error_msg));
+                            continue;
+                        }
+                        Some(fallback_error) => {
+                            error_msg = format!(
+                                "{} (fallback {} failed: {})",
+                                error_msg,
+                                fallback.name(),
+                                fallback_error
+                            );
+                        }
+                    }
+                }
+
                 let failure = if is_middleware_failure {
                     EventFailure::middleware_failure(event.name().to_string(), error_msg)
                 } else {
@@ -216,7 +353,7 @@ impl EventChain {
                 match self.fault_tolerance {
                     FaultToleranceMode::Strict => {
                         // Strict: Stop on any failure
-                        return ChainResult::failure(failures);
+                        return ChainResult::failure(failures).with_fallbacks(fallbacks);
                     }
                     FaultToleranceMode::Lenient => {
                         // Lenient: Continue on all failures
@@ -225,7 +362,7 @@ impl EventChain {
                     FaultToleranceMode::BestEffort => {
                         if is_middleware_failure {
                             // BestEffort: Stop on middleware failures
-                            return ChainResult::failure(failures);
+                            return ChainResult::failure(failures).with_fallbacks(fallbacks);
                         } else {
                             // BestEffort: Continue on event failures
                             continue;
@@ -236,10 +373,10 @@ impl EventChain {
         }
 
         // Determine final result
-        if failures.is_empty() {
+        if failures.is_empty() && fallbacks.is_empty() {
             ChainResult::success()
         } else {
-            ChainResult::partial_success(failures)
+            ChainResult::partial_success(failures).with_fallbacks(fallbacks)
         }
     }
 
@@ -248,6 +385,9 @@ impl EventChain {
         event: &dyn ChainableEvent,
         context: &mut EventContext,
     ) -> EventResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("event", name = event.name()).entered();
+
         if self.middlewares.is_empty() {
             return event.execute(context);
         }
@@ -296,3 +436,147 @@ impl fmt::Display for ChainStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Warn(&'static str);
+
+    impl ChainableEvent for Warn {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            context.add_warning(self.0, format!("{} is slow", self.0));
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    struct Nested(EventChain);
+
+    impl ChainableEvent for Nested {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            let result = self.0.execute(context);
+            assert_eq!(result.warnings.len(), 1);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Nested"
+        }
+    }
+
+    fn warned_events(result: &ChainResult) -> Vec<&str> {
+        result.warnings.iter().map(|w| w.event_name.as_str()).collect()
+    }
+
+    #[test]
+    fn nested_chain_keeps_outer_warnings() {
+        let inner = EventChain::new().event(Warn("Inner"));
+        let outer = EventChain::new()
+            .event(Warn("Before"))
+            .event(Nested(inner))
+            .event(Warn("After"));
+        let mut context = EventContext::new();
+
+        let result = outer.execute(&mut context);
+        assert_eq!(warned_events(&result), ["Before", "After"]);
+        assert_eq!(result.status, ChainStatus::CompletedWithWarnings);
+        assert!(context.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn warnings_already_in_the_context_stay_there() {
+        let chain = EventChain::new().event(Warn("Event"));
+        let mut context = EventContext::new();
+        context.add_warning("Caller", "recorded before the run".to_string());
+
+        let result = chain.execute(&mut context);
+        assert_eq!(warned_events(&result), ["Event"]);
+        assert_eq!(context.take_warnings().len(), 1);
+    }
+
+    struct Outcome(&'static str, Result<(), &'static str>);
+
+    impl ChainableEvent for Outcome {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            if let Some(error) = context.get::<String>(FALLBACK_ERROR_KEY) {
+                context.set("seen_error", error);
+            }
+            match self.1 {
+                Ok(()) => EventResult::Success(()),
+                Err(error) => EventResult::Failure(error.to_string()),
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    struct Unavailable;
+
+    impl EventMiddleware for Unavailable {
+        fn execute(
+            &self,
+            _event: &dyn ChainableEvent,
+            _context: &mut EventContext,
+            _next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+        ) -> EventResult<()> {
+            EventResult::MiddlewareFailure("service unavailable".to_string())
+        }
+    }
+
+    #[test]
+    fn successful_fallback_completes_with_the_original_failure_recorded() {
+        let chain = EventChain::new()
+            .event_with_fallback(Outcome("Fetch", Err("timeout")), Outcome("Cached", Ok(())));
+        let mut context = EventContext::new();
+
+        let result = chain.execute(&mut context);
+        assert_eq!(result.status, ChainStatus::CompletedWithWarnings);
+        assert!(result.failures.is_empty());
+        assert_eq!(result.fallbacks.len(), 1);
+        assert_eq!(result.fallbacks[0].event_name, "Fetch");
+        assert_eq!(result.fallbacks[0].error_message, "timeout");
+    }
+
+    #[test]
+    fn failed_fallback_fails_the_step_with_both_errors() {
+        let chain = EventChain::new()
+            .event_with_fallback(Outcome("Fetch", Err("timeout")), Outcome("Cached", Err("empty")));
+
+        let result = chain.execute(&mut EventContext::new());
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert!(result.fallbacks.is_empty());
+        assert_eq!(result.failures[0].error_message, "timeout (fallback Cached failed: empty)");
+    }
+
+    #[test]
+    fn fallback_sees_the_original_error_only_while_it_runs() {
+        let chain = EventChain::new()
+            .event_with_fallback(Outcome("Fetch", Err("timeout")), Outcome("Cached", Ok(())));
+        let mut context = EventContext::new();
+
+        chain.execute(&mut context);
+        assert_eq!(context.get::<String>("seen_error").as_deref(), Some("timeout"));
+        assert!(!context.has(FALLBACK_ERROR_KEY));
+        assert!(!context.has(FALLBACK_EVENT_KEY));
+    }
+
+    #[test]
+    fn middleware_failure_does_not_trigger_the_fallback() {
+        let chain = EventChain::new()
+            .middleware(Unavailable)
+            .event_with_fallback(Outcome("Fetch", Ok(())), Outcome("Cached", Ok(())));
+        let mut context = EventContext::new();
+
+        let result = chain.execute(&mut context);
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert!(result.fallbacks.is_empty());
+        assert_eq!(result.failures[0].error_message, "service unavailable");
+        assert!(!context.has("seen_error"));
+    }
+}
@@ -1,31 +1,121 @@
+use crate::core::event_failure::EventFailure;
 use hashbrown::HashMap;
 use std::any::Any;
+use std::sync::Arc;
 
 // Context that flows through the event chain
+//
+// Values are reference-counted, so `clone()` is a cheap snapshot: both
+// contexts share the stored values, and later `set`/`remove` calls on one
+// do not affect the other.
+#[derive(Clone)]
 pub struct EventContext {
-    data: HashMap<String, Box<dyn Any + Send + Sync>>,
+    data: HashMap<String, Arc<dyn Any + Send + Sync>>,
+    warnings: Vec<EventFailure>,
+    /// Top-level chain executions started with this context
+    run: u64,
+    /// Chains currently executing with this context (nested chains count too)
+    depth: u32,
 }
 
 impl EventContext {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
+            warnings: Vec::new(),
+            run: 0,
+            depth: 0,
         }
     }
 
     pub fn set<T: Any + Send + Sync>(&mut self, key: &str, value: T) {
-        self.data.insert(key.to_string(), Box::new(value));
+        self.data.insert(key.to_string(), Arc::new(value));
     }
 
     pub fn get<T: Any + Send + Sync + Clone>(&self, key: &str) -> Option<T> {
         self.data
             .get(key)
-            .and_then(|boxed| boxed.downcast_ref::<T>().cloned())
+            .and_then(|value| value.downcast_ref::<T>().cloned())
+    }
+
+    /// Get a value without knowing its type, e.g. to render it generically
+    pub fn get_raw(&self, key: &str) -> Option<&(dyn Any + Send + Sync)> {
+        self.data.get(key).map(|value| value.as_ref())
+    }
+
+    /// Get the shared handle to a value, to put it back later with `set_shared`
+    pub(crate) fn get_shared(&self, key: &str) -> Option<Arc<dyn Any + Send + Sync>> {
+        self.data.get(key).cloned()
+    }
+
+    pub(crate) fn set_shared(&mut self, key: &str, value: Arc<dyn Any + Send + Sync>) {
+        self.data.insert(key.to_string(), value);
+    }
+
+    /// Iterate over all keys, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str)
+    }
+
+    /// Iterate over the keys holding a `T`, in no particular order
+    pub fn keys_of_type<T: Any>(&self) -> impl Iterator<Item = &str> {
+        self.data
+            .iter()
+            .filter(|(_, value)| value.as_ref().is::<T>())
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Whether `key` holds a value of type `T`
+    pub fn is<T: Any>(&self, key: &str) -> bool {
+        self.data.get(key).is_some_and(|value| value.as_ref().is::<T>())
     }
 
     pub fn has(&self, key: &str) -> bool {
         self.data.contains_key(key)
     }
+
+    /// Remove a value, returning whether the key was present
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.data.remove(key).is_some()
+    }
+
+    /// Record a non-fatal problem, reported in `ChainResult::warnings`
+    ///
+    /// Middleware use this for conditions that should be surfaced without
+    /// failing the event, such as an exceeded time budget.
+    pub fn add_warning(&mut self, event_name: &str, message: String) {
+        self.warnings
+            .push(EventFailure::new(event_name.to_string(), message));
+    }
+
+    /// Remove and return all recorded warnings
+    pub fn take_warnings(&mut self) -> Vec<EventFailure> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Mark the start of a chain execution, setting aside the caller's warnings
+    pub(crate) fn begin_chain(&mut self) -> Vec<EventFailure> {
+        if self.depth == 0 {
+            self.run += 1;
+        }
+        self.depth += 1;
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Mark the end of a chain execution, returning its warnings and putting
+    /// back the caller's
+    pub(crate) fn end_chain(&mut self, outer_warnings: Vec<EventFailure>) -> Vec<EventFailure> {
+        self.depth = self.depth.saturating_sub(1);
+        std::mem::replace(&mut self.warnings, outer_warnings)
+    }
+
+    /// Identifies the current top-level chain execution
+    ///
+    /// Chains executed by an event on the same context share the id of the
+    /// chain that runs them.
+    pub(crate) fn run_id(&self) -> u64 {
+        self.run
+    }
 }
 
 impl Default for EventContext {
@@ -33,3 +123,23 @@ impl Default for EventContext {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_of_type_filters_by_stored_type() {
+        let mut context = EventContext::new();
+        context.set("name", "ada".to_string());
+        context.set("title", "Countess".to_string());
+        context.set("born", 1815i64);
+        context.set("alias", "ada");
+
+        let mut keys: Vec<_> = context.keys_of_type::<String>().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["name", "title"]);
+        assert_eq!(context.keys().count(), 4);
+        assert!(context.is::<i64>("born") && !context.is::<String>("alias"));
+    }
+}
@@ -0,0 +1,341 @@
+use crate::core::event_context::EventContext;
+use crate::core::event_result::EventResult;
+use crate::events::chainable_event::ChainableEvent;
+use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+struct BulkheadState {
+    active: usize,
+    queued: usize,
+}
+
+/// Why a call did not get a slot
+enum AcquireError {
+    /// The bulkhead (and its queue) is full, or the queue wait timed out
+    Rejected(String),
+    /// The state lock is poisoned
+    Poisoned,
+}
+
+/// Releases an execution slot when dropped, even if the event panics
+struct Permit<'a> {
+    shared: &'a (Mutex<BulkheadState>, Condvar),
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let (lock, condvar) = self.shared;
+        if let Ok(mut state) = lock.lock() {
+            state.active -= 1;
+        }
+        condvar.notify_one();
+    }
+}
+
+/// Middleware that caps how many events may execute concurrently
+///
+/// Where [`RateLimitMiddleware`](crate::middleware::rate_limit::RateLimitMiddleware)
+/// limits requests per second, the bulkhead limits requests *in flight*. This
+/// is useful when one chain is shared between threads and the downstream
+/// resource (a connection pool, a DB) can only handle a fixed number of
+/// simultaneous calls.
+///
+/// Clones share the same slots and counters.
+///
+/// # Middleware Failures
+///
+/// Rejections are load shedding and return `EventResult::Failure`; only a
+/// poisoned state lock returns `MiddlewareFailure`.
+///
+/// # Queueing
+///
+/// * No queue (default): calls beyond `max_concurrent` are rejected immediately
+/// * Bounded queue: up to `max_queued` calls wait for a free slot; further calls are rejected
+/// * Queue timeout: queued calls that do not get a slot in time are rejected.
+///   The timeout is measured on the bulkhead's [`Clock`]; with a `ManualClock`
+///   it only expires once the clock has been advanced past it.
+///
+/// # Example
+///
+/// ```ignore
+/// use event_chains::middleware::bulkhead::BulkheadMiddleware;
+/// use std::time::Duration;
+///
+/// // At most 4 concurrent DB writes, up to 16 waiting for at most 500ms
+/// let bulkhead = BulkheadMiddleware::new(4)
+///     .with_queue(16)
+///     .with_queue_timeout(Duration::from_millis(500));
+///
+/// let chain = EventChain::new()
+///     .middleware(bulkhead.clone())
+///     .event(WriteToDatabase);
+///
+/// println!("active: {}, queued: {}", bulkhead.active_count(), bulkhead.queued_count());
+/// ```
+#[derive(Clone)]
+pub struct BulkheadMiddleware {
+    shared: Arc<(Mutex<BulkheadState>, Condvar)>,
+    max_concurrent: usize,
+    max_queued: usize,
+    queue_timeout: Option<Duration>,
+    log_rejections: bool,
+    clock: SharedClock,
+}
+
+impl BulkheadMiddleware {
+    /// Create a bulkhead allowing `max_concurrent` simultaneous executions, with no wait queue
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is zero: such a bulkhead rejects every call.
+    pub fn new(max_concurrent: usize) -> Self {
+        assert!(max_concurrent >= 1, "bulkhead must allow at least 1 concurrent execution");
+        Self {
+            shared: Arc::new((
+                Mutex::new(BulkheadState {
+                    active: 0,
+                    queued: 0,
+                }),
+                Condvar::new(),
+            )),
+            max_concurrent,
+            max_queued: 0,
+            queue_timeout: None,
+            log_rejections: true,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// Allow up to `max_queued` calls to wait for a free slot instead of being rejected
+    pub fn with_queue(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// Reject queued calls that have not obtained a slot within `timeout`
+    ///
+    /// Without a timeout, queued calls wait indefinitely.
+    pub fn with_queue_timeout(mut self, timeout: Duration) -> Self {
+        self.queue_timeout = Some(timeout);
+        self
+    }
+
+    /// Measure queue timeouts with `clock` instead of the system clock
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Configure whether to log rejections
+    pub fn with_logging(mut self, enabled: bool) -> Self {
+        self.log_rejections = enabled;
+        self
+    }
+
+    /// Number of events currently executing
+    pub fn active_count(&self) -> usize {
+        self.shared.0.lock().map(|s| s.active).unwrap_or(0)
+    }
+
+    /// Number of events currently waiting for a slot
+    pub fn queued_count(&self) -> usize {
+        self.shared.0.lock().map(|s| s.queued).unwrap_or(0)
+    }
+
+    /// Get the maximum number of concurrent executions
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    fn acquire(&self) -> Result<Permit<'_>, AcquireError> {
+        let (lock, condvar) = &*self.shared;
+        let mut state = lock.lock().map_err(|_| AcquireError::Poisoned)?;
+
+        if state.active < self.max_concurrent {
+            state.active += 1;
+            return Ok(Permit {
+                shared: &self.shared,
+            });
+        }
+
+        if state.queued >= self.max_queued {
+            return Err(AcquireError::Rejected(format!(
+                "Bulkhead full: {} concurrent executions and {} queued",
+                state.active, state.queued
+            )));
+        }
+
+        state.queued += 1;
+        let deadline = self.queue_timeout.map(|timeout| self.clock.now() + timeout);
+
+        while state.active >= self.max_concurrent {
+            state = match deadline {
+                Some(deadline) => {
+                    let now = self.clock.now();
+                    if now >= deadline {
+                        state.queued -= 1;
+                        return Err(AcquireError::Rejected(format!(
+                            "Bulkhead queue timeout after {:?}",
+                            self.queue_timeout.unwrap_or_default()
+                        )));
+                    }
+                    match condvar.wait_timeout(state, deadline - now) {
+                        Ok((guard, _)) => guard,
+                        Err(_) => return Err(AcquireError::Poisoned),
+                    }
+                }
+                None => condvar.wait(state).map_err(|_| AcquireError::Poisoned)?,
+            };
+        }
+
+        state.queued -= 1;
+        state.active += 1;
+        Ok(Permit {
+            shared: &self.shared,
+        })
+    }
+}
+
+impl EventMiddleware for BulkheadMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        match self.acquire() {
+            Ok(_permit) => next(context),
+            Err(AcquireError::Poisoned) => EventResult::MiddlewareFailure(format!(
+                "Bulkhead infrastructure failure: state lock poisoned for {}",
+                event.name()
+            )),
+            Err(AcquireError::Rejected(reason)) => {
+                if self.log_rejections {
+                    emit(
+                        LogLevel::Warn,
+                        "bulkhead",
+                        event.name(),
+                        &format!("Bulkhead rejected {}: {}", event.name(), reason),
+                    );
+                }
+                // Load shedding is a policy decision, not infrastructure failure
+                // Use Failure, not MiddlewareFailure
+                EventResult::Failure(format!("{} for {}", reason, event.name()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::sync::mpsc;
+    use std::thread;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    fn call(bulkhead: &BulkheadMiddleware) -> EventResult<()> {
+        bulkhead.execute(&Call, &mut EventContext::new(), &mut |_| EventResult::Success(()))
+    }
+
+    /// Occupy a slot on another thread until the returned sender is dropped
+    fn hold_slot(bulkhead: &BulkheadMiddleware) -> (mpsc::Sender<()>, thread::JoinHandle<()>) {
+        let (release, released) = mpsc::channel::<()>();
+        let bulkhead_clone = bulkhead.clone();
+        let holder = thread::spawn(move || {
+            bulkhead_clone.execute(&Call, &mut EventContext::new(), &mut |_| {
+                let _ = released.recv();
+                EventResult::Success(())
+            });
+        });
+        wait_until(|| bulkhead.active_count() == 1);
+        (release, holder)
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        while !condition() {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 concurrent execution")]
+    fn new_rejects_zero_slots() {
+        BulkheadMiddleware::new(0);
+    }
+
+    #[test]
+    fn full_bulkhead_rejects_immediately_without_queue() {
+        let bulkhead = BulkheadMiddleware::new(1).with_logging(false);
+        let (release, holder) = hold_slot(&bulkhead);
+
+        let result = call(&bulkhead);
+        assert!(matches!(result, EventResult::Failure(ref e) if e.contains("Bulkhead full")));
+
+        drop(release);
+        holder.join().unwrap();
+        assert!(call(&bulkhead).is_success());
+    }
+
+    #[test]
+    fn bounded_queue_waits_for_a_slot_and_rejects_overflow() {
+        let bulkhead = BulkheadMiddleware::new(1).with_queue(1).with_logging(false);
+        let (release, holder) = hold_slot(&bulkhead);
+
+        let queued = {
+            let bulkhead = bulkhead.clone();
+            thread::spawn(move || call(&bulkhead))
+        };
+        wait_until(|| bulkhead.queued_count() == 1);
+        assert!(!call(&bulkhead).is_success());
+
+        drop(release);
+        holder.join().unwrap();
+        assert!(queued.join().unwrap().is_success());
+        assert_eq!((bulkhead.active_count(), bulkhead.queued_count()), (0, 0));
+    }
+
+    #[test]
+    fn queued_call_times_out() {
+        let bulkhead = BulkheadMiddleware::new(1)
+            .with_queue(1)
+            .with_queue_timeout(Duration::from_millis(20))
+            .with_logging(false);
+        let (release, holder) = hold_slot(&bulkhead);
+
+        let result = call(&bulkhead);
+        assert!(matches!(result, EventResult::Failure(ref e) if e.contains("queue timeout")));
+        assert_eq!(bulkhead.queued_count(), 0);
+
+        drop(release);
+        holder.join().unwrap();
+    }
+
+    #[test]
+    fn slot_is_released_when_the_event_panics() {
+        let bulkhead = BulkheadMiddleware::new(1).with_logging(false);
+
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            bulkhead.execute(&Call, &mut EventContext::new(), &mut |_| panic!("event failed"))
+        }));
+
+        assert!(panicked.is_err());
+        assert_eq!(bulkhead.active_count(), 0);
+        assert!(call(&bulkhead).is_success());
+    }
+}
@@ -2,6 +2,11 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
+use crate::middleware::pattern::glob_match;
+use crate::middleware::rng::{SeededRng, random_seed};
 use std::sync::{Arc, Mutex};
 
 /// Types of chaos that can be injected
@@ -15,6 +20,117 @@ pub enum ChaosType {
     Latency,
     /// Randomly skip event execution entirely
     Skip,
+    /// Panic inside the event call, to test panic safety of the caller
+    Panic,
+    /// Remove a context key before the event runs
+    RemoveContextKey,
+    /// Overwrite a context value with garbage before the event runs
+    ///
+    /// String values are scrambled; values of other types are replaced by a
+    /// string, so typed reads of the key return `None`.
+    CorruptContextKey,
+    /// Run the event, then report failure anyway (a lost acknowledgement)
+    LostAcknowledgement,
+    /// Run the event but discard its context writes, reporting success
+    DropContextWrites,
+}
+
+/// When a [`ChaosRule`] injects a fault
+///
+/// Call numbers are 1-based and count the calls to events matched by the
+/// rule while chaos is enabled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChaosSchedule {
+    /// Inject with the given probability (0.0 to 1.0)
+    Probability(f64),
+    /// Inject only on the n-th call
+    NthCall(u64),
+    /// Inject on every call from `from` to `to` (inclusive)
+    CallRange { from: u64, to: u64 },
+    /// Inject on every n-th call (n, 2n, 3n, ...)
+    EveryNth(u64),
+}
+
+impl ChaosSchedule {
+    fn fires(&self, call: u64, rng: &mut SeededRng) -> bool {
+        match *self {
+            ChaosSchedule::Probability(probability) => rng.next_f64() < probability,
+            ChaosSchedule::NthCall(n) => call == n,
+            ChaosSchedule::CallRange { from, to } => (from..=to).contains(&call),
+            ChaosSchedule::EveryNth(n) => n > 0 && call.is_multiple_of(n),
+        }
+    }
+}
+
+/// Fault injection targeted at specific events
+///
+/// `event_pattern` is an event name, optionally with `*` wildcards
+/// (`"ChargeCard"`, `"Payment*"`, `"*"`). If `chaos_types` is empty the
+/// middleware's [`ChaosConfig::chaos_types`] are used.
+///
+/// # Example
+///
+/// ```ignore
+/// // Fail the 3rd call to ChargeCard
+/// ChaosRule::new("ChargeCard").on_call(3).inject(ChaosType::RandomFailure);
+///
+/// // Fail calls 10-20 to any Payment event
+/// ChaosRule::new("Payment*").on_calls(10, 20);
+///
+/// // Delay half of all inventory lookups
+/// ChaosRule::new("*Inventory*").with_probability(0.5).inject(ChaosType::Latency);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosRule {
+    pub event_pattern: String,
+    pub schedule: ChaosSchedule,
+    pub chaos_types: Vec<ChaosType>,
+}
+
+impl ChaosRule {
+    /// Create a rule for matching events that always injects a fault
+    pub fn new(event_pattern: &str) -> Self {
+        Self {
+            event_pattern: event_pattern.to_string(),
+            schedule: ChaosSchedule::Probability(1.0),
+            chaos_types: Vec::new(),
+        }
+    }
+
+    /// Inject with the given probability
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        self.schedule = ChaosSchedule::Probability(probability.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Inject only on the n-th matching call (1-based)
+    pub fn on_call(mut self, n: u64) -> Self {
+        self.schedule = ChaosSchedule::NthCall(n);
+        self
+    }
+
+    /// Inject on matching calls `from` to `to` (inclusive, 1-based)
+    pub fn on_calls(mut self, from: u64, to: u64) -> Self {
+        self.schedule = ChaosSchedule::CallRange { from, to };
+        self
+    }
+
+    /// Inject on every n-th matching call
+    pub fn every_nth_call(mut self, n: u64) -> Self {
+        self.schedule = ChaosSchedule::EveryNth(n);
+        self
+    }
+
+    /// Add a fault type this rule may inject
+    pub fn inject(mut self, chaos_type: ChaosType) -> Self {
+        self.chaos_types.push(chaos_type);
+        self
+    }
+
+    /// Whether this rule applies to `event_name`
+    pub fn matches(&self, event_name: &str) -> bool {
+        glob_match(&self.event_pattern, event_name)
+    }
 }
 
 /// Configuration for chaos injection
@@ -28,6 +144,16 @@ pub struct ChaosConfig {
     pub min_latency_ms: u64,
     /// Maximum latency in milliseconds (for Latency chaos)
     pub max_latency_ms: u64,
+    /// Seed for the random number generator
+    ///
+    /// `None` picks a random seed, which is recorded in [`ChaosStats::seed`]
+    /// so the run can be replayed by setting it here.
+    pub seed: Option<u64>,
+    /// Targeted rules, evaluated in order before the global probability
+    pub rules: Vec<ChaosRule>,
+    /// Keys that `RemoveContextKey`/`CorruptContextKey` may target
+    /// (empty: any key present in the context)
+    pub target_keys: Vec<String>,
 }
 
 impl Default for ChaosConfig {
@@ -37,6 +163,9 @@ impl Default for ChaosConfig {
             chaos_types: vec![ChaosType::RandomFailure],
             min_latency_ms: 50,
             max_latency_ms: 500,
+            seed: None,
+            rules: Vec::new(),
+            target_keys: Vec::new(),
         }
     }
 }
@@ -44,12 +173,20 @@ impl Default for ChaosConfig {
 /// Statistics about chaos injection
 #[derive(Debug, Clone, Default)]
 pub struct ChaosStats {
+    /// Seed of the random number generator; pass it to
+    /// [`ChaosMiddleware::with_seed`] to replay the same fault sequence
+    pub seed: u64,
     pub total_events: u64,
     pub chaos_injected: u64,
     pub failures_injected: u64,
     pub infrastructure_failures_injected: u64,
     pub latency_injected: u64,
     pub skips_injected: u64,
+    pub panics_injected: u64,
+    /// Context keys removed or corrupted
+    pub context_faults_injected: u64,
+    pub lost_acks_injected: u64,
+    pub dropped_writes_injected: u64,
 }
 
 /// Middleware that randomly injects failures for testing resilience
@@ -72,6 +209,28 @@ pub struct ChaosStats {
 /// * `InfrastructureFailure` - Returns `EventResult::MiddlewareFailure` (infrastructure problem)
 /// * `Latency` - Injects random delays before executing the event
 /// * `Skip` - Skips event execution entirely (returns success without running)
+/// * `Panic` - Panics instead of running the event; the panic propagates out of
+///   `EventChain::execute`, so wrap the call in `std::panic::catch_unwind`
+/// * `RemoveContextKey` / `CorruptContextKey` - Tamper with a context value the
+///   event may depend on (see [`ChaosConfig::target_keys`]), then run it
+/// * `LostAcknowledgement` - Runs the event, then returns `EventResult::Failure`
+///   as if the acknowledgement was lost; side effects have already happened
+/// * `DropContextWrites` - Runs the event, then restores the context as it was
+///   before and returns success
+///
+/// # Targeting
+///
+/// [`ChaosRule`]s scope faults to events by name or pattern, with their own
+/// probability or a deterministic schedule. For each event, the first rule
+/// that fires decides the fault; if none fires, the global `probability`
+/// applies. Use a global probability of `0.0` to inject only via rules.
+///
+/// # Reproducibility
+///
+/// All random decisions come from one seeded generator shared by clones.
+/// The seed is recorded in [`ChaosStats::seed`] and printed by
+/// [`print_stats`](Self::print_stats); running the same chain with the same
+/// seed (single-threaded) injects exactly the same faults.
 ///
 /// # BestEffort Mode Interaction
 ///
@@ -100,6 +259,7 @@ pub struct ChaosStats {
 ///     ],
 ///     min_latency_ms: 100,
 ///     max_latency_ms: 1000,
+///     ..Default::default()
 /// };
 ///
 /// let chaos = ChaosMiddleware::with_config(config);
@@ -110,13 +270,24 @@ pub struct ChaosStats {
 ///
 /// // Check stats after execution
 /// chaos.print_stats();
+///
+/// // Replay a failing run
+/// let chaos = ChaosMiddleware::new(0.2).with_seed(0x5eed);
+///
+/// // Scripted scenario: only the 3rd ChargeCard call fails
+/// let chaos = ChaosMiddleware::new(0.0)
+///     .with_rule(ChaosRule::new("ChargeCard").on_call(3).inject(ChaosType::RandomFailure));
 /// ```
 #[derive(Clone)]
 pub struct ChaosMiddleware {
     config: ChaosConfig,
     stats: Arc<Mutex<ChaosStats>>,
+    rng: Arc<Mutex<SeededRng>>,
+    /// Matching calls seen by each rule, indexed like `config.rules`
+    rule_calls: Arc<Mutex<Vec<u64>>>,
     enabled: Arc<Mutex<bool>>,
     log_chaos: bool,
+    clock: SharedClock,
 }
 
 impl ChaosMiddleware {
@@ -129,15 +300,53 @@ impl ChaosMiddleware {
     }
 
     /// Create chaos middleware with full configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_latency_ms` is greater than `max_latency_ms`.
     pub fn with_config(config: ChaosConfig) -> Self {
+        assert!(
+            config.min_latency_ms <= config.max_latency_ms,
+            "min latency must not exceed max latency"
+        );
+        let seed = config.seed.unwrap_or_else(random_seed);
+        let rule_count = config.rules.len();
         Self {
             config,
-            stats: Arc::new(Mutex::new(ChaosStats::default())),
+            stats: Arc::new(Mutex::new(ChaosStats {
+                seed,
+                ..Default::default()
+            })),
+            rng: Arc::new(Mutex::new(SeededRng::new(seed))),
+            rule_calls: Arc::new(Mutex::new(vec![0; rule_count])),
             enabled: Arc::new(Mutex::new(true)),
             log_chaos: true,
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Use a fixed seed, e.g. the one recorded in [`ChaosStats::seed`] by a failing run
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self.rng = Arc::new(Mutex::new(SeededRng::new(seed)));
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.seed = seed;
+        }
+        self
+    }
+
+    /// Add a targeted fault rule
+    pub fn with_rule(mut self, rule: ChaosRule) -> Self {
+        self.config.rules.push(rule);
+        self.rule_calls = Arc::new(Mutex::new(vec![0; self.config.rules.len()]));
+        self
+    }
+
+    /// Get the seed of the random number generator
+    pub fn seed(&self) -> u64 {
+        self.stats.lock().map(|s| s.seed).unwrap_or_default()
+    }
+
     /// Enable or disable chaos injection at runtime
     pub fn set_enabled(&self, enabled: bool) {
         if let Ok(mut e) = self.enabled.lock() {
@@ -156,15 +365,30 @@ impl ChaosMiddleware {
         self
     }
 
+    /// Inject latency by sleeping on `clock` instead of the system clock
+    ///
+    /// With a `ManualClock`, injected latency advances the clock without
+    /// blocking, so timeouts downstream still observe it.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Get current chaos statistics
     pub fn get_stats(&self) -> Option<ChaosStats> {
         self.stats.lock().ok().map(|s| s.clone())
     }
 
-    /// Reset statistics
+    /// Reset statistics (the seed is kept) and restart rule call counts
     pub fn reset_stats(&self) {
         if let Ok(mut stats) = self.stats.lock() {
-            *stats = ChaosStats::default();
+            *stats = ChaosStats {
+                seed: stats.seed,
+                ..Default::default()
+            };
+        }
+        if let Ok(mut calls) = self.rule_calls.lock() {
+            calls.iter_mut().for_each(|count| *count = 0);
         }
     }
 
@@ -172,6 +396,7 @@ impl ChaosMiddleware {
     pub fn print_stats(&self) {
         if let Ok(stats) = self.stats.lock() {
             println!("\n=== Chaos Injection Statistics ===");
+            println!("Seed:                            {}", stats.seed);
             println!("Total events:                    {}", stats.total_events);
             println!("Chaos injected:                  {} ({:.1}%)",
                      stats.chaos_injected,
@@ -185,62 +410,102 @@ impl ChaosMiddleware {
             println!("  - Infrastructure failures:     {}", stats.infrastructure_failures_injected);
             println!("  - Latency injections:          {}", stats.latency_injected);
             println!("  - Skipped events:              {}", stats.skips_injected);
+            println!("  - Panics:                      {}", stats.panics_injected);
+            println!("  - Context keys tampered:       {}", stats.context_faults_injected);
+            println!("  - Lost acknowledgements:       {}", stats.lost_acks_injected);
+            println!("  - Dropped context writes:      {}", stats.dropped_writes_injected);
             println!();
         }
     }
 
-    fn should_inject_chaos(&self) -> bool {
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
+    fn with_rng<T>(&self, f: impl FnOnce(&mut SeededRng) -> T) -> T {
+        let mut rng = self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut rng)
+    }
 
-        // Use a simple random approach based on system time + a hash
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+    /// Decide which fault (if any) to inject into `event_name`
+    fn select_chaos(&self, event_name: &str) -> Option<ChaosType> {
+        let mut rule_calls = self
+            .rule_calls
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Every matching rule counts the call, even if an earlier rule fires
+        let mut fired = None;
+        for (rule, calls) in self.config.rules.iter().zip(rule_calls.iter_mut()) {
+            if !rule.matches(event_name) {
+                continue;
+            }
+            *calls += 1;
+            if fired.is_none() && self.with_rng(|rng| rule.schedule.fires(*calls, rng)) {
+                fired = Some(rule);
+            }
+        }
+        drop(rule_calls);
 
-        let random_value = (hasher.finish() % 10000) as f64 / 10000.0;
-        random_value < self.config.probability
+        match fired {
+            Some(rule) if !rule.chaos_types.is_empty() => Some(self.pick(&rule.chaos_types)),
+            Some(_) => Some(self.pick(&self.config.chaos_types)),
+            None if self.with_rng(|rng| rng.next_f64()) < self.config.probability => {
+                Some(self.pick(&self.config.chaos_types))
+            }
+            None => None,
+        }
     }
 
-    fn select_chaos_type(&self) -> ChaosType {
-        if self.config.chaos_types.is_empty() {
-            return ChaosType::RandomFailure;
+    /// Pick a context key to remove or corrupt, if any is present
+    fn pick_context_key(&self, context: &EventContext) -> Option<String> {
+        let mut keys: Vec<String> = if self.config.target_keys.is_empty() {
+            context.keys().map(String::from).collect()
+        } else {
+            self.config
+                .target_keys
+                .iter()
+                .filter(|key| context.has(key))
+                .cloned()
+                .collect()
+        };
+        if keys.is_empty() {
+            return None;
         }
 
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
+        // Sort so the same seed picks the same key regardless of hash order
+        keys.sort();
+        let index = self.with_rng(|rng| rng.next_index(keys.len()));
+        Some(keys.swap_remove(index))
+    }
 
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+    fn corrupt_value(context: &mut EventContext, key: &str) {
+        let corrupted = match context.get::<String>(key) {
+            Some(value) => value.chars().rev().collect::<String>() + "\u{FFFD}",
+            None => "\u{FFFD}chaos-corrupted".to_string(),
+        };
+        context.set(key, corrupted);
+    }
 
-        let idx = (hasher.finish() as usize) % self.config.chaos_types.len();
-        self.config.chaos_types[idx]
+    fn log_injection(&self, event_name: &str, message: String) {
+        if self.log_chaos {
+            emit(LogLevel::Warn, "chaos", event_name, &message);
+        }
     }
 
-    fn random_latency_ms(&self) -> u64 {
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
+    fn pick(&self, chaos_types: &[ChaosType]) -> ChaosType {
+        if chaos_types.is_empty() {
+            return ChaosType::RandomFailure;
+        }
 
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+        chaos_types[self.with_rng(|rng| rng.next_index(chaos_types.len()))]
+    }
+
+    fn random_latency_ms(&self) -> u64 {
+        let hash = self.with_rng(|rng| rng.next_u64());
 
         let range = self.config.max_latency_ms - self.config.min_latency_ms;
         if range == 0 {
             return self.config.min_latency_ms;
         }
 
-        self.config.min_latency_ms + (hasher.finish() % range)
+        self.config.min_latency_ms + (hash % range)
     }
 }
 
@@ -261,23 +526,25 @@ impl EventMiddleware for ChaosMiddleware {
             return next(context);
         }
 
-        // Decide whether to inject chaos
-        if !self.should_inject_chaos() {
+        // Decide whether (and which) chaos to inject
+        let Some(chaos_type) = self.select_chaos(event.name()) else {
             return next(context);
-        }
+        };
 
         // Record chaos injection
         if let Ok(mut stats) = self.stats.lock() {
             stats.chaos_injected += 1;
         }
 
-        // Select and execute chaos type
-        let chaos_type = self.select_chaos_type();
-
         match chaos_type {
             ChaosType::RandomFailure => {
                 if self.log_chaos {
-                    println!("    [CHAOS] Injecting random failure in {}", event.name());
+                    emit(
+                        LogLevel::Warn,
+                        "chaos",
+                        event.name(),
+                        &format!("[CHAOS] Injecting random failure in {}", event.name()),
+                    );
                 }
                 if let Ok(mut stats) = self.stats.lock() {
                     stats.failures_injected += 1;
@@ -287,7 +554,15 @@ impl EventMiddleware for ChaosMiddleware {
 
             ChaosType::InfrastructureFailure => {
                 if self.log_chaos {
-                    println!("    [CHAOS] Injecting infrastructure failure in {}", event.name());
+                    emit(
+                        LogLevel::Warn,
+                        "chaos",
+                        event.name(),
+                        &format!(
+                            "[CHAOS] Injecting infrastructure failure in {}",
+                            event.name()
+                        ),
+                    );
                 }
                 if let Ok(mut stats) = self.stats.lock() {
                     stats.infrastructure_failures_injected += 1;
@@ -301,18 +576,32 @@ impl EventMiddleware for ChaosMiddleware {
             ChaosType::Latency => {
                 let latency_ms = self.random_latency_ms();
                 if self.log_chaos {
-                    println!("    [CHAOS] Injecting {}ms latency in {}", latency_ms, event.name());
+                    emit(
+                        LogLevel::Warn,
+                        "chaos",
+                        event.name(),
+                        &format!(
+                            "[CHAOS] Injecting {}ms latency in {}",
+                            latency_ms,
+                            event.name()
+                        ),
+                    );
                 }
                 if let Ok(mut stats) = self.stats.lock() {
                     stats.latency_injected += 1;
                 }
-                std::thread::sleep(std::time::Duration::from_millis(latency_ms));
+                self.clock.sleep(std::time::Duration::from_millis(latency_ms));
                 next(context)
             }
 
             ChaosType::Skip => {
                 if self.log_chaos {
-                    println!("   ️  [CHAOS] Skipping execution of {}", event.name());
+                    emit(
+                        LogLevel::Warn,
+                        "chaos",
+                        event.name(),
+                        &format!("[CHAOS] Skipping execution of {}", event.name()),
+                    );
                 }
                 if let Ok(mut stats) = self.stats.lock() {
                     stats.skips_injected += 1;
@@ -320,6 +609,83 @@ impl EventMiddleware for ChaosMiddleware {
                 // Return success without executing the event
                 EventResult::Success(())
             }
+
+            ChaosType::Panic => {
+                self.log_injection(
+                    event.name(),
+                    format!("[CHAOS] Injecting panic in {}", event.name()),
+                );
+                if let Ok(mut stats) = self.stats.lock() {
+                    stats.panics_injected += 1;
+                }
+                panic!("Chaos monkey struck: panic in {}", event.name());
+            }
+
+            ChaosType::RemoveContextKey | ChaosType::CorruptContextKey => {
+                let Some(key) = self.pick_context_key(context) else {
+                    self.log_injection(
+                        event.name(),
+                        format!("[CHAOS] No context key to tamper with in {}", event.name()),
+                    );
+                    return next(context);
+                };
+
+                let action = if chaos_type == ChaosType::RemoveContextKey {
+                    context.remove(&key);
+                    "Removing"
+                } else {
+                    Self::corrupt_value(context, &key);
+                    "Corrupting"
+                };
+                self.log_injection(
+                    event.name(),
+                    format!("[CHAOS] {} context key '{}' in {}", action, key, event.name()),
+                );
+                if let Ok(mut stats) = self.stats.lock() {
+                    stats.context_faults_injected += 1;
+                }
+                next(context)
+            }
+
+            ChaosType::LostAcknowledgement => {
+                self.log_injection(
+                    event.name(),
+                    format!("[CHAOS] Dropping acknowledgement of {}", event.name()),
+                );
+                if let Ok(mut stats) = self.stats.lock() {
+                    stats.lost_acks_injected += 1;
+                }
+                let result = next(context);
+                if result.is_success() {
+                    EventResult::Failure(format!(
+                        "Chaos monkey struck: acknowledgement lost for {}",
+                        event.name()
+                    ))
+                } else {
+                    result
+                }
+            }
+
+            ChaosType::DropContextWrites => {
+                self.log_injection(
+                    event.name(),
+                    format!("[CHAOS] Dropping context writes of {}", event.name()),
+                );
+                if let Ok(mut stats) = self.stats.lock() {
+                    stats.dropped_writes_injected += 1;
+                }
+                // Keep warnings (earlier ones and those raised while the event
+                // ran); discard everything else the event wrote
+                let mut warnings = context.take_warnings();
+                let snapshot = context.clone();
+                let _ = next(context);
+                warnings.extend(context.take_warnings());
+                *context = snapshot;
+                for warning in warnings {
+                    context.add_warning(&warning.event_name, warning.error_message);
+                }
+                EventResult::Success(())
+            }
         }
     }
 }
@@ -329,3 +695,285 @@ impl Default for ChaosMiddleware {
         Self::new(0.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::clock::ManualClock;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    /// Run `calls` events through `chaos`, describing what happened to each
+    fn run(chaos: &ChaosMiddleware, clock: &ManualClock, calls: usize) -> Vec<String> {
+        (0..calls)
+            .map(|_| {
+                let before = clock.elapsed();
+                let mut ran = false;
+                let result = chaos.execute(&Call, &mut EventContext::new(), &mut |_| {
+                    ran = true;
+                    EventResult::Success(())
+                });
+                format!("{:?} ran={} slept={:?}", result, ran, clock.elapsed() - before)
+            })
+            .collect()
+    }
+
+    fn mixed_chaos(seed: Option<u64>, clock: &ManualClock) -> ChaosMiddleware {
+        ChaosMiddleware::with_config(ChaosConfig {
+            probability: 0.5,
+            chaos_types: vec![
+                ChaosType::RandomFailure,
+                ChaosType::InfrastructureFailure,
+                ChaosType::Latency,
+                ChaosType::Skip,
+            ],
+            seed,
+            ..Default::default()
+        })
+        .with_logging(false)
+        .with_clock(clock.clone())
+    }
+
+    #[test]
+    fn same_seed_injects_the_same_faults() {
+        let (first_clock, second_clock) = (ManualClock::new(), ManualClock::new());
+        let first = mixed_chaos(Some(0x5eed), &first_clock);
+        let second = mixed_chaos(Some(0x5eed), &second_clock);
+
+        let outcomes = run(&first, &first_clock, 50);
+        assert_eq!(outcomes, run(&second, &second_clock, 50));
+        assert_eq!(first.get_stats().unwrap().seed, 0x5eed);
+        assert!(first.get_stats().unwrap().chaos_injected > 0);
+    }
+
+    #[test]
+    fn random_seed_is_recorded_and_replays_the_run() {
+        let (first_clock, replay_clock) = (ManualClock::new(), ManualClock::new());
+        let first = mixed_chaos(None, &first_clock);
+        let outcomes = run(&first, &first_clock, 50);
+
+        let seed = first.get_stats().unwrap().seed;
+        assert_eq!(seed, first.seed());
+        let replay = mixed_chaos(None, &replay_clock).with_seed(seed);
+        assert_eq!(run(&replay, &replay_clock, 50), outcomes);
+    }
+
+    #[test]
+    #[should_panic(expected = "min latency must not exceed max latency")]
+    fn with_config_rejects_inverted_latency_range() {
+        ChaosMiddleware::with_config(ChaosConfig {
+            min_latency_ms: 500,
+            max_latency_ms: 50,
+            ..Default::default()
+        });
+    }
+
+    struct Named(&'static str);
+
+    impl ChainableEvent for Named {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    /// 1-based numbers of the calls to `event` that failed
+    fn failed_calls(chaos: &ChaosMiddleware, event: &'static str, calls: u64) -> Vec<u64> {
+        (1..=calls)
+            .filter(|_| {
+                let result = chaos.execute(&Named(event), &mut EventContext::new(), &mut |_| {
+                    EventResult::Success(())
+                });
+                !result.is_success()
+            })
+            .collect()
+    }
+
+    fn scripted(rule: ChaosRule) -> ChaosMiddleware {
+        ChaosMiddleware::new(0.0).with_rule(rule).with_logging(false)
+    }
+
+    #[test]
+    fn schedules_fire_on_the_expected_calls() {
+        let nth = scripted(ChaosRule::new("ChargeCard").on_call(3));
+        assert_eq!(failed_calls(&nth, "ChargeCard", 10), [3]);
+
+        let range = scripted(ChaosRule::new("ChargeCard").on_calls(4, 6));
+        assert_eq!(failed_calls(&range, "ChargeCard", 10), [4, 5, 6]);
+
+        let every = scripted(ChaosRule::new("ChargeCard").every_nth_call(3));
+        assert_eq!(failed_calls(&every, "ChargeCard", 10), [3, 6, 9]);
+
+        let never = scripted(ChaosRule::new("ChargeCard").every_nth_call(0));
+        assert!(failed_calls(&never, "ChargeCard", 10).is_empty());
+    }
+
+    #[test]
+    fn rules_count_only_matching_calls() {
+        let chaos = scripted(ChaosRule::new("Payment*").on_call(2));
+
+        assert!(failed_calls(&chaos, "ShipOrder", 5).is_empty());
+        assert_eq!(failed_calls(&chaos, "PaymentCapture", 1), []);
+        assert_eq!(failed_calls(&chaos, "PaymentRefund", 1), [1]);
+    }
+
+    #[test]
+    fn first_firing_rule_picks_the_fault() {
+        let chaos = ChaosMiddleware::new(0.0)
+            .with_rule(ChaosRule::new("ChargeCard").inject(ChaosType::InfrastructureFailure))
+            .with_rule(ChaosRule::new("*").inject(ChaosType::RandomFailure))
+            .with_logging(false);
+        let call = |event: &'static str| {
+            let mut context = EventContext::new();
+            chaos.execute(&Named(event), &mut context, &mut |_| EventResult::Success(()))
+        };
+
+        assert!(call("ChargeCard").is_middleware_failure());
+        assert!(call("ShipOrder").is_event_failure());
+    }
+
+    #[test]
+    fn reset_stats_restarts_rule_call_counts() {
+        let chaos = scripted(ChaosRule::new("ChargeCard").on_call(2));
+        assert_eq!(failed_calls(&chaos, "ChargeCard", 3), [2]);
+
+        chaos.reset_stats();
+        assert_eq!(failed_calls(&chaos, "ChargeCard", 3), [2]);
+    }
+
+    fn always(chaos_type: ChaosType) -> ChaosMiddleware {
+        ChaosMiddleware::with_config(ChaosConfig {
+            probability: 1.0,
+            chaos_types: vec![chaos_type],
+            seed: Some(1),
+            ..Default::default()
+        })
+        .with_logging(false)
+    }
+
+    #[test]
+    fn drop_context_writes_rolls_back_writes_but_keeps_warnings() {
+        let chaos = always(ChaosType::DropContextWrites);
+        let mut context = EventContext::new();
+        context.set("order", "A-1".to_string());
+        context.add_warning("Earlier", "recorded before".to_string());
+
+        let result = chaos.execute(&Call, &mut context, &mut |context| {
+            context.set("order", "A-2".to_string());
+            context.set("receipt", 42u32);
+            context.add_warning("Call", "recorded while running".to_string());
+            EventResult::Success(())
+        });
+
+        assert!(result.is_success());
+        assert_eq!(context.get::<String>("order").as_deref(), Some("A-1"));
+        assert!(!context.has("receipt"));
+        let warnings = context.take_warnings();
+        let warned: Vec<_> = warnings.iter().map(|w| w.event_name.as_str()).collect();
+        assert_eq!(warned, ["Earlier", "Call"]);
+        assert_eq!(chaos.get_stats().unwrap().dropped_writes_injected, 1);
+    }
+
+    #[test]
+    fn lost_acknowledgement_runs_the_event_then_fails() {
+        let chaos = always(ChaosType::LostAcknowledgement);
+        let mut context = EventContext::new();
+
+        let result = chaos.execute(&Call, &mut context, &mut |context| {
+            context.set("charged", true);
+            EventResult::Success(())
+        });
+
+        let lost = |e: &str| e.contains("acknowledgement lost");
+        assert!(matches!(result, EventResult::Failure(ref e) if lost(e)));
+        assert_eq!(context.get::<bool>("charged"), Some(true));
+
+        // A failing event keeps its own error
+        let result = chaos.execute(&Call, &mut context, &mut |_| {
+            EventResult::Failure("declined".to_string())
+        });
+        assert!(matches!(result, EventResult::Failure(ref e) if e == "declined"));
+    }
+
+    #[test]
+    fn panic_fault_panics_without_running_the_event() {
+        let chaos = always(ChaosType::Panic);
+        let mut ran = false;
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            chaos.execute(&Call, &mut EventContext::new(), &mut |_| {
+                ran = true;
+                EventResult::Success(())
+            })
+        }));
+
+        assert!(panicked.is_err());
+        assert!(!ran);
+        assert_eq!(chaos.get_stats().unwrap().panics_injected, 1);
+    }
+
+    #[test]
+    fn remove_context_key_only_touches_target_keys() {
+        let chaos = ChaosMiddleware::with_config(ChaosConfig {
+            probability: 1.0,
+            chaos_types: vec![ChaosType::RemoveContextKey],
+            target_keys: vec!["user_id".to_string(), "missing".to_string()],
+            seed: Some(1),
+            ..Default::default()
+        })
+        .with_logging(false);
+        let mut context = EventContext::new();
+        context.set("user_id", "u-1".to_string());
+        context.set("order", "A-1".to_string());
+
+        let mut seen = None;
+        chaos.execute(&Call, &mut context, &mut |context| {
+            seen = Some((context.has("user_id"), context.has("order")));
+            EventResult::Success(())
+        });
+
+        assert_eq!(seen, Some((false, true)));
+        assert_eq!(chaos.get_stats().unwrap().context_faults_injected, 1);
+    }
+
+    #[test]
+    fn corrupt_context_key_scrambles_strings_and_replaces_other_types() {
+        let chaos = always(ChaosType::CorruptContextKey);
+
+        let mut context = EventContext::new();
+        context.set("user_id", "abc".to_string());
+        chaos.execute(&Call, &mut context, &mut |_| EventResult::Success(()));
+        assert_eq!(context.get::<String>("user_id").as_deref(), Some("cba\u{FFFD}"));
+
+        let mut context = EventContext::new();
+        context.set("amount", 100u64);
+        chaos.execute(&Call, &mut context, &mut |_| EventResult::Success(()));
+        assert_eq!(context.get::<u64>("amount"), None);
+        assert!(context.is::<String>("amount"));
+    }
+
+    #[test]
+    fn context_fault_without_keys_runs_the_event_untouched() {
+        let chaos = always(ChaosType::RemoveContextKey);
+
+        let result = chaos.execute(&Call, &mut EventContext::new(), &mut |_| {
+            EventResult::Success(())
+        });
+
+        assert!(result.is_success());
+        assert_eq!(chaos.get_stats().unwrap().context_faults_injected, 0);
+    }
+}
@@ -0,0 +1,555 @@
+use crate::middleware::chaos::{ChaosMiddleware, ChaosStats};
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::metrics::MetricsMiddleware;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Probe of a steady-state hypothesis: returns whether it held and what was observed
+pub type ProbeFn = Arc<dyn Fn() -> (bool, String) + Send + Sync>;
+
+/// A measurable property of the system that should hold despite injected faults
+///
+/// # Example
+///
+/// ```ignore
+/// let metrics = MetricsMiddleware::new();
+///
+/// // At least 95% of all events succeed
+/// SteadyState::success_rate_at_least(&metrics, 95.0);
+///
+/// // p99 latency of ChargeCard stays under 200ms
+/// SteadyState::p99_latency_below(&metrics, "ChargeCard", Duration::from_millis(200));
+///
+/// // Anything else
+/// SteadyState::custom("queue is drained", move || {
+///     let depth = queue.len();
+///     (depth == 0, format!("queue depth {}", depth))
+/// });
+/// ```
+#[derive(Clone)]
+pub struct SteadyState {
+    description: String,
+    probe: ProbeFn,
+}
+
+impl SteadyState {
+    /// Hypothesis checked by an arbitrary probe
+    pub fn custom<F>(description: &str, probe: F) -> Self
+    where
+        F: Fn() -> (bool, String) + Send + Sync + 'static,
+    {
+        Self {
+            description: description.to_string(),
+            probe: Arc::new(probe),
+        }
+    }
+
+    /// Success rate over all events recorded by `metrics` is at least `percent`
+    pub fn success_rate_at_least(metrics: &MetricsMiddleware, percent: f64) -> Self {
+        let metrics = metrics.clone();
+        Self::custom(&format!("success rate >= {:.1}%", percent), move || {
+            let (total, successful) = metrics
+                .get_all_metrics()
+                .iter()
+                .fold((0, 0), |(total, successful), m| {
+                    (total + m.total_executions, successful + m.successful_executions)
+                });
+            if total == 0 {
+                return (false, "no executions recorded".to_string());
+            }
+            let rate = successful as f64 / total as f64 * 100.0;
+            (rate >= percent, format!("{:.1}% of {} executions", rate, total))
+        })
+    }
+
+    /// Success rate of `event_name` is at least `percent`
+    pub fn event_success_rate_at_least(
+        metrics: &MetricsMiddleware,
+        event_name: &str,
+        percent: f64,
+    ) -> Self {
+        let metrics = metrics.clone();
+        let event_name = event_name.to_string();
+        Self::custom(
+            &format!("{} success rate >= {:.1}%", event_name, percent),
+            move || match metrics.get_metrics(&event_name) {
+                Some(m) if m.total_executions > 0 => (
+                    m.success_rate() >= percent,
+                    format!("{:.1}% of {} executions", m.success_rate(), m.total_executions),
+                ),
+                _ => (false, "no executions recorded".to_string()),
+            },
+        )
+    }
+
+    /// 99th percentile latency of `event_name` is below `max`
+    pub fn p99_latency_below(metrics: &MetricsMiddleware, event_name: &str, max: Duration) -> Self {
+        let metrics = metrics.clone();
+        let event_name = event_name.to_string();
+        Self::custom(
+            &format!("{} p99 latency < {:?}", event_name, max),
+            move || match metrics.get_metrics(&event_name) {
+                Some(m) if m.total_executions > 0 => {
+                    let p99 = Duration::from_micros(m.p99_micros());
+                    (p99 < max, format!("p99 {:?}", p99))
+                }
+                _ => (false, "no executions recorded".to_string()),
+            },
+        )
+    }
+
+    /// Human-readable statement of the hypothesis
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn check(&self) -> Observation {
+        let (held, observed) = (self.probe)();
+        Observation {
+            hypothesis: self.description.clone(),
+            observed,
+            held,
+        }
+    }
+}
+
+/// Phases of a chaos experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExperimentPhase {
+    /// Chaos disabled: verify the steady state holds to begin with
+    Baseline,
+    /// Chaos enabled: does the steady state survive the faults?
+    Injection,
+    /// Chaos disabled again (after the rollback action): does the system recover?
+    Rollback,
+}
+
+/// Result of checking one hypothesis at the end of a phase
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub hypothesis: String,
+    pub observed: String,
+    pub held: bool,
+}
+
+/// Outcome of one experiment phase
+#[derive(Debug, Clone)]
+pub struct PhaseReport {
+    pub phase: ExperimentPhase,
+    /// Number of times the workload ran
+    pub iterations: u64,
+    pub duration: Duration,
+    pub observations: Vec<Observation>,
+}
+
+impl PhaseReport {
+    /// Whether every hypothesis held at the end of this phase
+    pub fn held(&self) -> bool {
+        self.observations.iter().all(|o| o.held)
+    }
+}
+
+/// Outcome of a chaos experiment
+#[derive(Debug, Clone)]
+pub struct ExperimentReport {
+    pub name: String,
+    pub phases: Vec<PhaseReport>,
+    /// Faults injected during the injection phase
+    pub chaos_stats: Option<ChaosStats>,
+}
+
+impl ExperimentReport {
+    /// Report of the given phase, if it ran
+    pub fn phase(&self, phase: ExperimentPhase) -> Option<&PhaseReport> {
+        self.phases.iter().find(|p| p.phase == phase)
+    }
+
+    /// Whether the experiment stopped because the baseline steady state did not hold
+    pub fn aborted(&self) -> bool {
+        self.phase(ExperimentPhase::Injection).is_none()
+    }
+
+    /// Whether the steady state held during injection and after rollback
+    pub fn hypothesis_held(&self) -> bool {
+        !self.aborted() && self.phases.iter().all(PhaseReport::held)
+    }
+
+    /// Print the report to stdout
+    pub fn print(&self) {
+        println!("\n=== Chaos Experiment: {} ===", self.name);
+        for phase in &self.phases {
+            println!(
+                "{:?}: {} ({} iterations in {:?})",
+                phase.phase,
+                if phase.held() { "steady state held" } else { "steady state VIOLATED" },
+                phase.iterations,
+                phase.duration
+            );
+            for o in &phase.observations {
+                println!(
+                    "  [{}] {} - observed {}",
+                    if o.held { "ok" } else { "FAIL" },
+                    o.hypothesis,
+                    o.observed
+                );
+            }
+        }
+        if let Some(stats) = &self.chaos_stats {
+            println!(
+                "Faults injected: {} of {} events (seed {})",
+                stats.chaos_injected, stats.total_events, stats.seed
+            );
+        }
+        let verdict = if self.aborted() {
+            "ABORTED (no steady state before injection)"
+        } else if self.hypothesis_held() {
+            "HYPOTHESIS HELD"
+        } else {
+            "HYPOTHESIS REJECTED"
+        };
+        println!("Result: {}", verdict);
+        println!();
+    }
+}
+
+/// How long each phase runs
+#[derive(Debug, Clone, Copy)]
+enum PhaseLength {
+    Iterations(u64),
+    Duration(Duration),
+}
+
+/// Puts the chaos middleware's enabled flag back when dropped, including on unwind
+struct RestoreEnabled {
+    chaos: ChaosMiddleware,
+    enabled: bool,
+}
+
+impl Drop for RestoreEnabled {
+    fn drop(&mut self) {
+        self.chaos.set_enabled(self.enabled);
+    }
+}
+
+/// A Chaos Monkey style experiment driven by a [`ChaosMiddleware`]
+///
+/// The experiment runs a workload (typically executing a chain) in three
+/// phases and checks the steady-state hypotheses at the end of each:
+///
+/// 1. **Baseline** - chaos disabled. If the hypotheses do not hold here the
+///    experiment is aborted, since faults could not be blamed for anything.
+/// 2. **Injection** - chaos enabled.
+/// 3. **Rollback** - the rollback action runs, chaos is disabled again, and
+///    the system must return to its steady state.
+///
+/// Before each phase the metrics registered with
+/// [`with_metrics`](Self::with_metrics) are reset, so hypotheses read from
+/// them only see that phase. The chaos middleware's enabled flag is restored
+/// afterwards.
+///
+/// # Example
+///
+/// ```ignore
+/// let metrics = MetricsMiddleware::new();
+/// let chaos = ChaosMiddleware::new(0.2).with_seed(42);
+/// // LIFO: metrics (outermost) -> retry -> chaos (innermost) -> event
+/// let chain = EventChain::new()
+///     .middleware(chaos.clone())
+///     .middleware(RetryMiddleware::new(3))
+///     .middleware(metrics.clone())
+///     .event(ChargeCard)
+///     .with_fault_tolerance(FaultToleranceMode::Lenient);
+///
+/// let report = ChaosExperiment::new("retries absorb transient failures", chaos)
+///     .with_metrics(metrics.clone())
+///     .with_hypothesis(SteadyState::success_rate_at_least(&metrics, 95.0))
+///     .with_iterations(500)
+///     .run(|| {
+///         chain.execute(&mut EventContext::new());
+///     });
+///
+/// report.print();
+/// assert!(report.hypothesis_held());
+/// ```
+pub struct ChaosExperiment {
+    name: String,
+    chaos: ChaosMiddleware,
+    hypotheses: Vec<SteadyState>,
+    metrics: Vec<MetricsMiddleware>,
+    rollback: Option<Box<dyn FnMut()>>,
+    length: PhaseLength,
+    clock: SharedClock,
+}
+
+impl ChaosExperiment {
+    /// Create an experiment that toggles `chaos` (100 workload iterations per phase)
+    pub fn new(name: &str, chaos: ChaosMiddleware) -> Self {
+        Self {
+            name: name.to_string(),
+            chaos,
+            hypotheses: Vec::new(),
+            metrics: Vec::new(),
+            rollback: None,
+            length: PhaseLength::Iterations(100),
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// Add a steady-state hypothesis
+    pub fn with_hypothesis(mut self, hypothesis: SteadyState) -> Self {
+        self.hypotheses.push(hypothesis);
+        self
+    }
+
+    /// Reset `metrics` before each phase
+    pub fn with_metrics(mut self, metrics: MetricsMiddleware) -> Self {
+        self.metrics.push(metrics);
+        self
+    }
+
+    /// Action run at the start of the rollback phase, e.g. restoring test data
+    pub fn with_rollback<F: FnMut() + 'static>(mut self, rollback: F) -> Self {
+        self.rollback = Some(Box::new(rollback));
+        self
+    }
+
+    /// Run the workload `iterations` times per phase
+    pub fn with_iterations(mut self, iterations: u64) -> Self {
+        self.length = PhaseLength::Iterations(iterations);
+        self
+    }
+
+    /// Run the workload repeatedly for `duration` per phase
+    ///
+    /// With a `ManualClock`, the workload must advance the clock (e.g. through
+    /// middleware sleeping on it), otherwise the phase never ends.
+    pub fn with_phase_duration(mut self, duration: Duration) -> Self {
+        self.length = PhaseLength::Duration(duration);
+        self
+    }
+
+    /// Measure phase durations with `clock` instead of the system clock
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Run all phases, calling `workload` once per iteration
+    ///
+    /// The chaos middleware's enabled flag is restored afterwards, even if the
+    /// workload panics.
+    pub fn run<F: FnMut()>(mut self, mut workload: F) -> ExperimentReport {
+        let _restore = RestoreEnabled {
+            enabled: self.chaos.is_enabled(),
+            chaos: self.chaos.clone(),
+        };
+        let mut report = ExperimentReport {
+            name: self.name.clone(),
+            phases: Vec::new(),
+            chaos_stats: None,
+        };
+
+        self.chaos.set_enabled(false);
+        let baseline = self.run_phase(ExperimentPhase::Baseline, &mut workload);
+        let baseline_held = baseline.held();
+        report.phases.push(baseline);
+
+        if baseline_held {
+            self.chaos.reset_stats();
+            self.chaos.set_enabled(true);
+            report
+                .phases
+                .push(self.run_phase(ExperimentPhase::Injection, &mut workload));
+            self.chaos.set_enabled(false);
+            report.chaos_stats = self.chaos.get_stats();
+
+            if let Some(rollback) = self.rollback.as_mut() {
+                rollback();
+            }
+            report
+                .phases
+                .push(self.run_phase(ExperimentPhase::Rollback, &mut workload));
+        }
+
+        report
+    }
+
+    fn run_phase<F: FnMut()>(&self, phase: ExperimentPhase, workload: &mut F) -> PhaseReport {
+        for metrics in &self.metrics {
+            metrics.reset();
+        }
+
+        let start = self.clock.now();
+        let mut iterations = 0;
+        loop {
+            let done = match self.length {
+                PhaseLength::Iterations(n) => iterations >= n,
+                PhaseLength::Duration(d) => self.clock.now().duration_since(start) >= d,
+            };
+            if done {
+                break;
+            }
+            workload();
+            iterations += 1;
+        }
+
+        PhaseReport {
+            phase,
+            iterations,
+            duration: self.clock.now().duration_since(start),
+            observations: self.hypotheses.iter().map(SteadyState::check).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_context::EventContext;
+    use crate::core::event_result::EventResult;
+    use crate::events::chainable_event::ChainableEvent;
+    use crate::events::event_middleware::EventMiddleware;
+    use crate::middleware::clock::ManualClock;
+    use std::cell::{Cell, RefCell};
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::rc::Rc;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    fn holds() -> SteadyState {
+        SteadyState::custom("always", || (true, "ok".to_string()))
+    }
+
+    #[test]
+    fn failed_baseline_aborts_before_injection() {
+        let chaos = ChaosMiddleware::new(1.0);
+        let rolled_back = Rc::new(Cell::new(false));
+        let flag = rolled_back.clone();
+        let injected = Rc::new(Cell::new(0));
+        let probe = chaos.clone();
+        let count = injected.clone();
+
+        let report = ChaosExperiment::new("broken from the start", chaos.clone())
+            .with_hypothesis(SteadyState::custom("never", || (false, "down".to_string())))
+            .with_rollback(move || flag.set(true))
+            .with_iterations(3)
+            .run(|| {
+                if probe.is_enabled() {
+                    count.set(count.get() + 1);
+                }
+            });
+
+        assert!(report.aborted());
+        assert!(!report.hypothesis_held());
+        assert_eq!(report.phases.len(), 1);
+        assert_eq!(report.phases[0].phase, ExperimentPhase::Baseline);
+        assert!(report.chaos_stats.is_none());
+        assert_eq!(injected.get(), 0);
+        assert!(!rolled_back.get());
+    }
+
+    #[test]
+    fn phases_run_in_order_with_rollback_before_the_last() {
+        let chaos = ChaosMiddleware::new(0.0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let rollback_log = log.clone();
+        let workload_log = log.clone();
+        let probe = chaos.clone();
+
+        let report = ChaosExperiment::new("ordered", chaos.clone())
+            .with_hypothesis(holds())
+            .with_rollback(move || rollback_log.borrow_mut().push("rollback"))
+            .with_iterations(2)
+            .run(|| {
+                let phase = if probe.is_enabled() { "chaos" } else { "calm" };
+                workload_log.borrow_mut().push(phase);
+            });
+
+        let phases: Vec<_> = report.phases.iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            [ExperimentPhase::Baseline, ExperimentPhase::Injection, ExperimentPhase::Rollback]
+        );
+        assert!(report.phases.iter().all(|p| p.iterations == 2));
+        assert_eq!(
+            *log.borrow(),
+            ["calm", "calm", "chaos", "chaos", "rollback", "calm", "calm"]
+        );
+        assert!(report.hypothesis_held());
+        assert!(report.chaos_stats.is_some());
+        assert!(chaos.is_enabled());
+    }
+
+    #[test]
+    fn metrics_are_reset_before_each_phase() {
+        let chaos = ChaosMiddleware::new(0.0);
+        let metrics = MetricsMiddleware::new();
+        let probe = metrics.clone();
+        let hypothesis = SteadyState::custom("one phase of calls", move || {
+            let total = probe.get_metrics("Call").map_or(0, |m| m.total_executions);
+            (total == 3, format!("{} calls", total))
+        });
+
+        let report = ChaosExperiment::new("fresh metrics", chaos)
+            .with_metrics(metrics.clone())
+            .with_hypothesis(hypothesis)
+            .with_iterations(3)
+            .run(|| {
+                metrics.execute(&Call, &mut EventContext::new(), &mut |_| {
+                    EventResult::Success(())
+                });
+            });
+
+        assert!(report.hypothesis_held());
+        assert_eq!(report.phases.len(), 3);
+    }
+
+    #[test]
+    fn phase_duration_is_measured_on_the_experiment_clock() {
+        let chaos = ChaosMiddleware::new(0.0);
+        let clock = ManualClock::new();
+        let workload_clock = clock.clone();
+
+        let report = ChaosExperiment::new("timed", chaos)
+            .with_hypothesis(holds())
+            .with_clock(clock.clone())
+            .with_phase_duration(Duration::from_secs(10))
+            .run(|| workload_clock.advance(Duration::from_secs(3)));
+
+        for phase in &report.phases {
+            assert_eq!(phase.iterations, 4);
+            assert_eq!(phase.duration, Duration::from_secs(12));
+        }
+        assert_eq!(clock.elapsed(), Duration::from_secs(36));
+    }
+
+    #[test]
+    fn run_restores_enabled_flag_when_workload_panics() {
+        let chaos = ChaosMiddleware::new(0.0);
+        chaos.set_enabled(false);
+
+        let injected = chaos.clone();
+        let experiment =
+            ChaosExperiment::new("panicking workload", chaos.clone()).with_iterations(1);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            experiment.run(|| {
+                if injected.is_enabled() {
+                    panic!("workload failed during injection");
+                }
+            })
+        }));
+
+        assert!(result.is_err());
+        assert!(!chaos.is_enabled());
+    }
+}
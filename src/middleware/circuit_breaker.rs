@@ -2,9 +2,20 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Registry key used for the single circuit in [`CircuitScope::Global`] mode
+const GLOBAL_CIRCUIT: &str = "*";
+
+type StateChangeCallback = Arc<dyn Fn(&CircuitStateChange) + Send + Sync>;
+type ClassifierFn = Arc<dyn Fn(&EventResult<()>) -> bool + Send + Sync>;
+
 /// Circuit breaker states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CircuitState {
@@ -16,12 +27,207 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+/// A circuit state transition
+///
+/// Reported to [`CircuitBreakerMiddleware::on_state_change`] callbacks.
+#[derive(Debug, Clone)]
+pub struct CircuitStateChange {
+    /// Registry key of the circuit (see [`CircuitScope`] for the key format)
+    pub circuit: String,
+    /// Event whose execution triggered the transition
+    pub event_name: String,
+    pub from: CircuitState,
+    pub to: CircuitState,
+    /// Human-readable reason, e.g. `"5 consecutive failures"`
+    pub reason: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Decides which event results count as failures for the circuit breaker
+///
+/// Successful results are recorded as successes. Failures that are not classified
+/// as failures are not recorded at all: they neither count toward tripping nor
+/// reset the consecutive failure count, and they leave the sliding window alone.
+#[derive(Clone, Default)]
+pub enum FailureClassifier {
+    /// Count both `Failure` and `MiddlewareFailure` (default)
+    #[default]
+    AllFailures,
+    /// Count only `MiddlewareFailure`, ignoring business failures such as a wrong password
+    MiddlewareFailuresOnly,
+    /// Count results for which the predicate returns `true`
+    Custom(ClassifierFn),
+}
+
+impl FailureClassifier {
+    /// Create a classifier from a predicate
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Ignore validation errors, count everything else
+    /// let classifier = FailureClassifier::custom(|result| {
+    ///     result.get_error().is_some_and(|err| !err.starts_with("Validation"))
+    /// });
+    /// ```
+    pub fn custom<F>(predicate: F) -> Self
+    where
+        F: Fn(&EventResult<()>) -> bool + Send + Sync + 'static,
+    {
+        FailureClassifier::Custom(Arc::new(predicate))
+    }
+
+    /// Whether `result` counts as a failure
+    pub fn is_failure(&self, result: &EventResult<()>) -> bool {
+        match self {
+            FailureClassifier::AllFailures => result.is_failure(),
+            FailureClassifier::MiddlewareFailuresOnly => result.is_middleware_failure(),
+            FailureClassifier::Custom(predicate) => predicate(result),
+        }
+    }
+}
+
+impl fmt::Debug for FailureClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureClassifier::AllFailures => write!(f, "AllFailures"),
+            FailureClassifier::MiddlewareFailuresOnly => write!(f, "MiddlewareFailuresOnly"),
+            FailureClassifier::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Sliding window over which the failure rate is computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlidingWindow {
+    /// The most recent `n` calls
+    CountBased(usize),
+    /// All calls made within the given duration
+    TimeBased(Duration),
+}
+
+/// How events are mapped onto circuits
+///
+/// Each scope uses its own key prefix in the [`CircuitRegistry`], so event
+/// names and context values never share a circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitScope {
+    /// One circuit shared by every event in the chain (default), keyed `"*"`
+    Global,
+    /// An independent circuit per event name, keyed `"event:<name>"`
+    PerEvent,
+    /// An independent circuit per value of a `String` context key, keyed
+    /// `"ctx:<key>=<value>"`
+    ///
+    /// Events whose context does not contain the key fall back to their
+    /// [`PerEvent`](Self::PerEvent) circuit.
+    ContextKey(String),
+}
+
+/// Shared registry of circuits, keyed as described in [`CircuitScope`]
+///
+/// Cloning a registry produces a new handle to the same circuits, so it can
+/// be queried or reset while the middleware is owned by a chain.
+///
+/// # Example
+///
+/// ```ignore
+/// let breaker = CircuitBreakerMiddleware::new().with_scope(CircuitScope::PerEvent);
+/// let registry = breaker.registry();
+///
+/// let chain = EventChain::new().middleware(breaker).event(ChargeCard);
+/// chain.execute(&mut context);
+///
+/// if registry.get_state("event:ChargeCard") == Some(CircuitState::Open) {
+///     registry.reset("event:ChargeCard");
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CircuitRegistry {
+    circuits: Arc<Mutex<HashMap<String, CircuitBreakerState>>>,
+}
+
+impl CircuitRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the state of a circuit, or `None` if no event has used it yet
+    pub fn get_state(&self, key: &str) -> Option<CircuitState> {
+        self.circuits.lock().unwrap().get(key).map(|c| c.state)
+    }
+
+    /// Get the state of every known circuit, sorted by key
+    pub fn states(&self) -> Vec<(String, CircuitState)> {
+        let circuits = self.circuits.lock().unwrap();
+        let mut states: Vec<_> = circuits
+            .iter()
+            .map(|(key, circuit)| (key.clone(), circuit.state))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        states
+    }
+
+    /// Reset a single circuit to closed state
+    ///
+    /// Returns `false` if the circuit does not exist.
+    pub fn reset(&self, key: &str) -> bool {
+        match self.circuits.lock().unwrap().get_mut(key) {
+            Some(circuit) => {
+                *circuit = CircuitBreakerState::new();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reset every circuit to closed state
+    pub fn reset_all(&self) {
+        for circuit in self.circuits.lock().unwrap().values_mut() {
+            *circuit = CircuitBreakerState::new();
+        }
+    }
+}
+
+/// A half-open trial call in flight; frees its slot when dropped, even if the event panics
+struct TrialSlot<'a> {
+    circuits: &'a Mutex<HashMap<String, CircuitBreakerState>>,
+    key: &'a str,
+}
+
+impl Drop for TrialSlot<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut circuits) = self.circuits.lock()
+            && let Some(state) = circuits.get_mut(self.key)
+        {
+            state.half_open_calls = state.half_open_calls.saturating_sub(1);
+        }
+    }
+}
+
+/// Failure-rate tripping configuration
+#[derive(Debug, Clone, Copy)]
+struct FailureRateConfig {
+    threshold_percent: f64,
+    window: SlidingWindow,
+}
+
+struct CallRecord {
+    at: Instant,
+    failed: bool,
+}
+
 struct CircuitBreakerState {
     state: CircuitState,
     failure_count: u32,
     success_count: u32,
     last_failure_time: Option<Instant>,
     opened_at: Option<Instant>,
+    /// Trial calls currently in flight, see [`TrialSlot`]
+    half_open_calls: u32,
+    window: VecDeque<CallRecord>,
 }
 
 impl CircuitBreakerState {
@@ -32,8 +238,46 @@ impl CircuitBreakerState {
             success_count: 0,
             last_failure_time: None,
             opened_at: None,
+            half_open_calls: 0,
+            window: VecDeque::new(),
         }
     }
+
+    /// Record a call in the sliding window and return the failure rate (0.0 - 100.0)
+    /// once at least `minimum_calls` calls are in the window
+    fn record_in_window(
+        &mut self,
+        config: &FailureRateConfig,
+        minimum_calls: usize,
+        failed: bool,
+        now: Instant,
+    ) -> Option<f64> {
+        self.window.push_back(CallRecord { at: now, failed });
+
+        match config.window {
+            SlidingWindow::CountBased(size) => {
+                while self.window.len() > size {
+                    self.window.pop_front();
+                }
+            }
+            SlidingWindow::TimeBased(duration) => {
+                while self
+                    .window
+                    .front()
+                    .is_some_and(|call| now.duration_since(call.at) > duration)
+                {
+                    self.window.pop_front();
+                }
+            }
+        }
+
+        if self.window.len() < minimum_calls.max(1) {
+            return None;
+        }
+
+        let failed = self.window.iter().filter(|call| call.failed).count();
+        Some(failed as f64 / self.window.len() as f64 * 100.0)
+    }
 }
 
 /// Middleware that implements the circuit breaker pattern
@@ -43,19 +287,45 @@ impl CircuitBreakerState {
 ///
 /// # Middleware Failures
 ///
-/// This middleware does NOT produce `MiddlewareFailure`. Circuit breaker state
-/// (open/closed) is a protection mechanism, not an infrastructure failure.
-/// When the circuit is open, it returns `EventResult::Failure`.
+/// Open-circuit rejections return `EventResult::Failure`; this middleware
+/// never returns `MiddlewareFailure`.
+///
+/// # Tripping Conditions
+///
+/// The circuit opens when either condition is met:
+/// - `failure_threshold` **consecutive** failures (default: 5)
+/// - The failure rate within a sliding window reaches a percentage, once the
+///   window holds a minimum number of calls (opt-in, see
+///   [`with_failure_rate_threshold`](Self::with_failure_rate_threshold))
+///
+/// With a slow-call threshold configured, calls that take longer than the
+/// threshold count as failures in the sliding window even if they succeed.
 ///
-/// # Rationale
+/// # Failure Classification
 ///
-/// The circuit breaker pattern is a business logic protection mechanism.
-/// An open circuit means "the downstream service is failing, don't call it",
-/// not "the circuit breaker infrastructure is broken". Therefore it returns
-/// `Failure` (business logic) not `MiddlewareFailure` (infrastructure).
+/// By default both `Failure` and `MiddlewareFailure` count toward tripping.
+/// Use [`with_failure_classifier`](Self::with_failure_classifier) to ignore
+/// business failures (e.g. a user entering a bad password) or to supply a
+/// custom predicate.
 ///
-/// In BestEffort mode, an open circuit will allow the chain to continue with
-/// other events, which is the desired behavior for resilient cleanup/recovery.
+/// # Half-Open Probing
+///
+/// When the timeout elapses the circuit moves to half-open and lets trial
+/// calls through. By default every call is a trial call; use
+/// [`with_half_open_max_calls`](Self::with_half_open_max_calls) to limit how
+/// many trial calls may be in flight at once and reject the rest.
+///
+/// # State Change Notifications
+///
+/// Register [`on_state_change`](Self::on_state_change) callbacks to receive a
+/// [`CircuitStateChange`] for every transition, e.g. to raise alerts.
+///
+/// # Scopes
+///
+/// By default one circuit protects the whole chain, so a failing event opens
+/// the circuit for every event. Use [`with_scope`](Self::with_scope) to keep
+/// an independent circuit per event name or per context value, and
+/// [`registry`](Self::registry) to inspect or reset individual circuits.
 ///
 /// # Example
 ///
@@ -70,13 +340,59 @@ impl CircuitBreakerState {
 ///             .with_timeout(Duration::from_secs(30))
 ///     )
 ///     .event(ExternalApiEvent);
+///
+/// // Open at 50% failures (or calls slower than 2s) over the last 20 calls
+/// let chain = EventChain::new()
+///     .middleware(
+///         CircuitBreakerMiddleware::new()
+///             .with_failure_rate_threshold(50.0, SlidingWindow::CountBased(20))
+///             .with_minimum_calls(10)
+///             .with_slow_call_threshold(Duration::from_secs(2))
+///     )
+///     .event(ExternalApiEvent);
+///
+/// // Allow 3 trial calls when half-open, and alert on transitions
+/// let chain = EventChain::new()
+///     .middleware(
+///         CircuitBreakerMiddleware::new()
+///             .with_half_open_max_calls(3)
+///             .on_state_change(|change| {
+///                 alerts.send(format!("{}: {:?} -> {:?} ({})",
+///                     change.circuit, change.from, change.to, change.reason));
+///             })
+///     )
+///     .event(ExternalApiEvent);
+///
+/// // Only infrastructure failures trip the circuit
+/// let chain = EventChain::new()
+///     .middleware(
+///         CircuitBreakerMiddleware::new()
+///             .with_failure_classifier(FailureClassifier::MiddlewareFailuresOnly)
+///     )
+///     .event(AuthenticateUser);
+///
+/// // One circuit per tenant
+/// let chain = EventChain::new()
+///     .middleware(
+///         CircuitBreakerMiddleware::new()
+///             .with_scope(CircuitScope::ContextKey("tenant_id".to_string()))
+///     )
+///     .event(ExternalApiEvent);
 /// ```
 pub struct CircuitBreakerMiddleware {
-    state: Arc<Mutex<CircuitBreakerState>>,
+    registry: CircuitRegistry,
+    scope: CircuitScope,
     failure_threshold: u32,
     success_threshold: u32,
     timeout: Duration,
+    failure_rate: Option<FailureRateConfig>,
+    minimum_calls: usize,
+    slow_call_threshold: Option<Duration>,
+    max_half_open_calls: Option<u32>,
+    classifier: FailureClassifier,
+    state_change_callbacks: Vec<StateChangeCallback>,
     log_state_changes: bool,
+    clock: SharedClock,
 }
 
 impl CircuitBreakerMiddleware {
@@ -88,11 +404,19 @@ impl CircuitBreakerMiddleware {
     /// - Timeout: 60 seconds
     pub fn new() -> Self {
         Self {
-            state: Arc::new(Mutex::new(CircuitBreakerState::new())),
+            registry: CircuitRegistry::new(),
+            scope: CircuitScope::Global,
             failure_threshold: 5,
             success_threshold: 2,
             timeout: Duration::from_secs(60),
+            failure_rate: None,
+            minimum_calls: 10,
+            slow_call_threshold: None,
+            max_half_open_calls: None,
+            classifier: FailureClassifier::AllFailures,
+            state_change_callbacks: Vec::new(),
             log_state_changes: true,
+            clock: SystemClock::shared(),
         }
     }
 
@@ -114,28 +438,162 @@ impl CircuitBreakerMiddleware {
         self
     }
 
+    /// Open the circuit when the failure rate within `window` reaches `percent` (0.0 - 100.0)
+    ///
+    /// The rate is only evaluated once the window holds the minimum number of
+    /// calls (default: 10, see [`with_minimum_calls`](Self::with_minimum_calls)).
+    /// The consecutive `failure_threshold` still applies; set it to `u32::MAX`
+    /// to trip on failure rate alone.
+    pub fn with_failure_rate_threshold(mut self, percent: f64, window: SlidingWindow) -> Self {
+        self.failure_rate = Some(FailureRateConfig {
+            threshold_percent: percent.clamp(0.0, 100.0),
+            window,
+        });
+        self
+    }
+
+    /// Set the minimum number of calls in the window before the failure rate is evaluated
+    ///
+    /// Has no effect unless a failure rate threshold is configured, which may
+    /// happen before or after this call.
+    pub fn with_minimum_calls(mut self, minimum_calls: usize) -> Self {
+        self.minimum_calls = minimum_calls;
+        self
+    }
+
+    /// Count calls slower than `threshold` as failures in the sliding window
+    ///
+    /// Has no effect unless a failure rate threshold is configured.
+    pub fn with_slow_call_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_call_threshold = Some(threshold);
+        self
+    }
+
+    /// Set which results count as failures (default: [`FailureClassifier::AllFailures`])
+    pub fn with_failure_classifier(mut self, classifier: FailureClassifier) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
+    /// Limit the number of trial calls in flight while half-open
+    ///
+    /// Further calls are rejected with `EventResult::Failure` until a trial
+    /// call completes (or panics) and frees its slot.
+    pub fn with_half_open_max_calls(mut self, max_calls: u32) -> Self {
+        self.max_half_open_calls = Some(max_calls.max(1));
+        self
+    }
+
+    /// Register a callback invoked on every circuit state transition
+    ///
+    /// Callbacks run on the thread that caused the transition, after the
+    /// circuit lock has been released. Multiple callbacks may be registered.
+    pub fn on_state_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CircuitStateChange) + Send + Sync + 'static,
+    {
+        self.state_change_callbacks.push(Arc::new(callback));
+        self
+    }
+
     /// Configure whether to log state changes
     pub fn with_logging(mut self, enabled: bool) -> Self {
         self.log_state_changes = enabled;
         self
     }
 
+    /// Use `clock` instead of the system clock for timeouts and slow-call detection
+    ///
+    /// With a `ManualClock`, tests can advance past the open timeout instantly.
+    /// Breakers sharing a registry should share the clock as well.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Set how events are mapped onto circuits (default: [`CircuitScope::Global`])
+    pub fn with_scope(mut self, scope: CircuitScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Use an existing registry, e.g. to share circuits between chains
+    pub fn with_registry(mut self, registry: CircuitRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Get a handle to the registry holding this breaker's circuits
+    pub fn registry(&self) -> CircuitRegistry {
+        self.registry.clone()
+    }
+
     /// Get the current circuit state
+    ///
+    /// In [`CircuitScope::Global`] mode this is the state of the single shared
+    /// circuit. In keyed scopes, returns `Open` if any circuit is open,
+    /// otherwise `HalfOpen` if any is half-open, otherwise `Closed`; use
+    /// [`registry`](Self::registry) to query individual circuits.
     pub fn get_state(&self) -> CircuitState {
-        self.state.lock().unwrap().state
+        let circuits = self.registry.circuits.lock().unwrap();
+        if self.scope == CircuitScope::Global {
+            return circuits
+                .get(GLOBAL_CIRCUIT)
+                .map(|c| c.state)
+                .unwrap_or(CircuitState::Closed);
+        }
+
+        let states: Vec<_> = circuits.values().map(|c| c.state).collect();
+        if states.contains(&CircuitState::Open) {
+            CircuitState::Open
+        } else if states.contains(&CircuitState::HalfOpen) {
+            CircuitState::HalfOpen
+        } else {
+            CircuitState::Closed
+        }
     }
 
-    /// Manually reset the circuit breaker to closed state
+    /// Manually reset all circuits to closed state
+    ///
+    /// Unlike [`CircuitRegistry::reset_all`], this notifies state change callbacks.
     pub fn reset(&self) {
-        let mut state = self.state.lock().unwrap();
-        state.state = CircuitState::Closed;
-        state.failure_count = 0;
-        state.success_count = 0;
-        state.last_failure_time = None;
-        state.opened_at = None;
+        let mut changes = Vec::new();
+        {
+            let mut circuits = self.registry.circuits.lock().unwrap();
+            for (key, circuit) in circuits.iter_mut() {
+                if circuit.state != CircuitState::Closed {
+                    changes.push(self.state_change(
+                        key,
+                        "",
+                        circuit.state,
+                        CircuitState::Closed,
+                        "manual reset".to_string(),
+                    ));
+                }
+                *circuit = CircuitBreakerState::new();
+            }
+        }
 
         if self.log_state_changes {
-            println!(" Circuit breaker manually reset to CLOSED");
+            emit(
+                LogLevel::Info,
+                "circuit_breaker",
+                "",
+                "Circuit breaker manually reset to CLOSED",
+            );
+        }
+
+        self.notify(&changes);
+    }
+
+    fn circuit_key(&self, event: &dyn ChainableEvent, context: &EventContext) -> String {
+        match &self.scope {
+            CircuitScope::Global => GLOBAL_CIRCUIT.to_string(),
+            CircuitScope::PerEvent => format!("event:{}", event.name()),
+            CircuitScope::ContextKey(key) => match context.get::<String>(key) {
+                Some(value) => format!("ctx:{}={}", key, value),
+                None => format!("event:{}", event.name()),
+            },
         }
     }
 
@@ -145,14 +603,96 @@ impl CircuitBreakerMiddleware {
         }
 
         if let Some(opened_at) = state.opened_at {
-            Instant::now().duration_since(opened_at) >= self.timeout
+            self.clock.now().duration_since(opened_at) >= self.timeout
         } else {
             false
         }
     }
 
-    fn record_success(&self, event_name: &str) {
-        let mut state = self.state.lock().unwrap();
+    fn state_change(
+        &self,
+        circuit: &str,
+        event_name: &str,
+        from: CircuitState,
+        to: CircuitState,
+        reason: String,
+    ) -> CircuitStateChange {
+        CircuitStateChange {
+            circuit: circuit.to_string(),
+            event_name: event_name.to_string(),
+            from,
+            to,
+            reason,
+            timestamp: self
+                .clock
+                .system_time()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Move a circuit to a new state, log it, and queue the change for callbacks
+    fn transition(
+        &self,
+        state: &mut CircuitBreakerState,
+        to: CircuitState,
+        circuit: &str,
+        event_name: &str,
+        reason: String,
+        changes: &mut Vec<CircuitStateChange>,
+    ) {
+        let from = state.state;
+        state.state = to;
+        state.success_count = 0;
+        state.window.clear();
+
+        match to {
+            CircuitState::Open => state.opened_at = Some(self.clock.now()),
+            CircuitState::Closed => {
+                state.opened_at = None;
+                state.failure_count = 0;
+            }
+            CircuitState::HalfOpen => {}
+        }
+
+        if self.log_state_changes {
+            let label = match (from, to) {
+                (CircuitState::HalfOpen, CircuitState::Open) => "re-OPENED",
+                (_, CircuitState::Open) => "OPENED",
+                (_, CircuitState::HalfOpen) => "HALF-OPEN",
+                (_, CircuitState::Closed) => "CLOSED",
+            };
+            let level = match to {
+                CircuitState::Open => LogLevel::Warn,
+                _ => LogLevel::Info,
+            };
+            emit(
+                level,
+                "circuit_breaker",
+                event_name,
+                &format!("Circuit breaker {} for {} ({})", label, event_name, reason),
+            );
+        }
+
+        changes.push(self.state_change(circuit, event_name, from, to, reason));
+    }
+
+    fn notify(&self, changes: &[CircuitStateChange]) {
+        for change in changes {
+            for callback in &self.state_change_callbacks {
+                callback(change);
+            }
+        }
+    }
+
+    fn record_success(
+        &self,
+        state: &mut CircuitBreakerState,
+        circuit: &str,
+        event_name: &str,
+        changes: &mut Vec<CircuitStateChange>,
+    ) {
         state.failure_count = 0;
 
         match state.state {
@@ -161,52 +701,66 @@ impl CircuitBreakerMiddleware {
             }
             CircuitState::HalfOpen => {
                 state.success_count += 1;
-                if state.success_count >= self.success_threshold {
-                    state.state = CircuitState::Closed;
-                    state.success_count = 0;
-                    state.opened_at = None;
 
-                    if self.log_state_changes {
-                        println!(" Circuit breaker CLOSED for {}", event_name);
-                    }
+                if state.success_count >= self.success_threshold {
+                    let reason = format!("{} successful trial calls", state.success_count);
+                    let to = CircuitState::Closed;
+                    self.transition(state, to, circuit, event_name, reason, changes);
                 }
             }
             CircuitState::Open => {
-                // Shouldn't happen, but handle it
-                state.state = CircuitState::Closed;
-                state.success_count = 0;
-                state.opened_at = None;
+                // Result of a call admitted before the circuit (re-)opened; ignore it
             }
         }
     }
 
-    fn record_failure(&self, event_name: &str) {
-        let mut state = self.state.lock().unwrap();
-        state.last_failure_time = Some(Instant::now());
+    fn record_window(
+        &self,
+        state: &mut CircuitBreakerState,
+        failed: bool,
+        circuit: &str,
+        event_name: &str,
+        changes: &mut Vec<CircuitStateChange>,
+    ) {
+        let Some(config) = &self.failure_rate else {
+            return;
+        };
+
+        // The window only tracks calls made while closed; half-open uses success_threshold
+        if state.state != CircuitState::Closed {
+            return;
+        }
+
+        let now = self.clock.now();
+        if let Some(rate) = state.record_in_window(config, self.minimum_calls, failed, now)
+            && rate >= config.threshold_percent
+        {
+            let reason = format!("failure rate: {:.1}% over {} calls", rate, state.window.len());
+            self.transition(state, CircuitState::Open, circuit, event_name, reason, changes);
+        }
+    }
+
+    fn record_failure(
+        &self,
+        state: &mut CircuitBreakerState,
+        circuit: &str,
+        event_name: &str,
+        changes: &mut Vec<CircuitStateChange>,
+    ) {
+        state.last_failure_time = Some(self.clock.now());
 
         match state.state {
             CircuitState::Closed => {
                 state.failure_count += 1;
                 if state.failure_count >= self.failure_threshold {
-                    state.state = CircuitState::Open;
-                    state.opened_at = Some(Instant::now());
-
-                    if self.log_state_changes {
-                        println!(
-                            " Circuit breaker OPENED for {} (failures: {})",
-                            event_name, state.failure_count
-                        );
-                    }
+                    let reason = format!("failures: {}", state.failure_count);
+                    let to = CircuitState::Open;
+                    self.transition(state, to, circuit, event_name, reason, changes);
                 }
             }
             CircuitState::HalfOpen => {
-                state.state = CircuitState::Open;
-                state.success_count = 0;
-                state.opened_at = Some(Instant::now());
-
-                if self.log_state_changes {
-                    println!(" Circuit breaker re-OPENED for {}", event_name);
-                }
+                let reason = "trial call failed".to_string();
+                self.transition(state, CircuitState::Open, circuit, event_name, reason, changes);
             }
             CircuitState::Open => {
                 // Already open, just update the time
@@ -222,47 +776,77 @@ impl EventMiddleware for CircuitBreakerMiddleware {
         context: &mut EventContext,
         next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
     ) -> EventResult<()> {
-        // Check if we should attempt to close the circuit
-        {
-            let mut state = self.state.lock().unwrap();
-            if self.should_attempt_reset(&state) {
-                state.state = CircuitState::HalfOpen;
-                state.success_count = 0;
-
-                if self.log_state_changes {
-                    println!(" Circuit breaker HALF-OPEN for {}", event.name());
-                }
-            }
-        }
+        let key = self.circuit_key(event, context);
+        let mut changes = Vec::new();
 
-        // Check current state
-        let current_state = self.state.lock().unwrap().state;
+        // Check if we should attempt to close the circuit, then decide whether to admit the call
+        let admission = {
+            let mut circuits = self.registry.circuits.lock().unwrap();
+            let state = circuits
+                .entry(key.clone())
+                .or_insert_with(CircuitBreakerState::new);
 
-        match current_state {
-            CircuitState::Open => {
-                // Circuit breaker open is a protection mechanism, not infrastructure failure
-                // Use Failure, not MiddlewareFailure
-                return EventResult::Failure(format!(
-                    "Circuit breaker is OPEN for {}",
-                    event.name()
-                ));
+            if self.should_attempt_reset(state) {
+                let reason = format!("timeout of {:?} elapsed", self.timeout);
+                let to = CircuitState::HalfOpen;
+                self.transition(state, to, &key, event.name(), reason, &mut changes);
             }
-            CircuitState::Closed | CircuitState::HalfOpen => {
-                let result = next(context);
 
-                match &result {
-                    EventResult::Success(_) => {
-                        self.record_success(event.name());
-                    }
-                    EventResult::Failure(_) | EventResult::MiddlewareFailure(_) => {
-                        // Record both types of failures in circuit breaker
-                        self.record_failure(event.name());
+            match state.state {
+                CircuitState::Open => Err(format!("Circuit breaker is OPEN for {}", event.name())),
+                CircuitState::HalfOpen => match self.max_half_open_calls {
+                    Some(max) if state.half_open_calls >= max => Err(format!(
+                        "Circuit breaker is HALF-OPEN for {} (trial call limit of {} reached)",
+                        event.name(),
+                        max
+                    )),
+                    _ => {
+                        state.half_open_calls += 1;
+                        Ok(Some(TrialSlot {
+                            circuits: &self.registry.circuits,
+                            key: &key,
+                        }))
                     }
-                }
+                },
+                CircuitState::Closed => Ok(None),
+            }
+        };
+        self.notify(&changes);
 
-                result
+        let trial = match admission {
+            Ok(trial) => trial,
+            // Circuit breaker open is a protection mechanism, not infrastructure failure
+            // Use Failure, not MiddlewareFailure
+            Err(reason) => return EventResult::Failure(reason),
+        };
+
+        let start = self.clock.now();
+        let result = next(context);
+        drop(trial);
+        let slow = self
+            .slow_call_threshold
+            .is_some_and(|threshold| self.clock.now().duration_since(start) > threshold);
+
+        let mut changes = Vec::new();
+        {
+            let mut circuits = self.registry.circuits.lock().unwrap();
+            let state = circuits
+                .entry(key.clone())
+                .or_insert_with(CircuitBreakerState::new);
+
+            if self.classifier.is_failure(&result) {
+                self.record_failure(state, &key, event.name(), &mut changes);
+                self.record_window(state, true, &key, event.name(), &mut changes);
+            } else if result.is_failure() {
+                // Ignored by the classifier, e.g. a wrong password: says nothing about health
+            } else {
+                self.record_success(state, &key, event.name(), &mut changes);
+                self.record_window(state, slow, &key, event.name(), &mut changes);
             }
         }
+        self.notify(&changes);
+
+        result
     }
 }
 
@@ -271,3 +855,421 @@ impl Default for CircuitBreakerMiddleware {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::clock::ManualClock;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    fn call(breaker: &CircuitBreakerMiddleware, succeed: bool) -> EventResult<()> {
+        let mut context = EventContext::new();
+        breaker.execute(&Call, &mut context, &mut |_| {
+            if succeed {
+                EventResult::Success(())
+            } else {
+                EventResult::Failure("down".to_string())
+            }
+        })
+    }
+
+    #[test]
+    fn minimum_calls_applies_regardless_of_builder_order() {
+        let before = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(u32::MAX)
+            .with_minimum_calls(2)
+            .with_failure_rate_threshold(50.0, SlidingWindow::CountBased(10));
+        let after = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(u32::MAX)
+            .with_failure_rate_threshold(50.0, SlidingWindow::CountBased(10))
+            .with_minimum_calls(2);
+
+        for breaker in [before, after] {
+            call(&breaker, true);
+            call(&breaker, false);
+            assert_eq!(breaker.get_state(), CircuitState::Open);
+        }
+    }
+
+    #[test]
+    fn open_circuit_recovers_through_half_open() {
+        let clock = ManualClock::new();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(2)
+            .with_success_threshold(2)
+            .with_timeout(Duration::from_secs(30))
+            .with_clock(clock.clone());
+
+        call(&breaker, false);
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+
+        let mut reached = false;
+        let rejected = breaker.execute(&Call, &mut EventContext::new(), &mut |_| {
+            reached = true;
+            EventResult::Success(())
+        });
+        assert!(!reached);
+        assert!(!rejected.is_success());
+
+        clock.advance(Duration::from_secs(30));
+        assert!(call(&breaker, true).is_success());
+        assert_eq!(breaker.get_state(), CircuitState::HalfOpen);
+        call(&breaker, true);
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn failed_trial_call_reopens_the_circuit() {
+        let clock = ManualClock::new();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_timeout(Duration::from_secs(30))
+            .with_clock(clock.clone());
+
+        call(&breaker, false);
+        clock.advance(Duration::from_secs(30));
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+
+        clock.advance(Duration::from_secs(29));
+        assert!(!call(&breaker, true).is_success());
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn count_based_window_keeps_only_the_latest_calls() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(u32::MAX)
+            .with_failure_rate_threshold(50.0, SlidingWindow::CountBased(4))
+            .with_minimum_calls(4);
+
+        for succeed in [false, true, true, true, true] {
+            call(&breaker, succeed);
+        }
+        // The first failure has left the window: 0 of 4 failed
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn time_based_window_forgets_old_failures() {
+        let clock = ManualClock::new();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(u32::MAX)
+            .with_failure_rate_threshold(50.0, SlidingWindow::TimeBased(Duration::from_secs(10)))
+            .with_minimum_calls(2)
+            .with_clock(clock.clone());
+
+        call(&breaker, false);
+        clock.advance(Duration::from_secs(11));
+        call(&breaker, true);
+        call(&breaker, true);
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        call(&breaker, false);
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    fn timed_call(breaker: &CircuitBreakerMiddleware, clock: &ManualClock, secs: u64) {
+        breaker.execute(&Call, &mut EventContext::new(), &mut |_| {
+            clock.advance(Duration::from_secs(secs));
+            EventResult::Success(())
+        });
+    }
+
+    #[test]
+    fn slow_successes_count_as_failures_in_the_window() {
+        let clock = ManualClock::new();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(u32::MAX)
+            .with_failure_rate_threshold(50.0, SlidingWindow::CountBased(4))
+            .with_minimum_calls(4)
+            .with_slow_call_threshold(Duration::from_secs(2))
+            .with_clock(clock.clone());
+
+        timed_call(&breaker, &clock, 1);
+        // Exactly at the threshold is not slow
+        timed_call(&breaker, &clock, 2);
+        timed_call(&breaker, &clock, 3);
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        timed_call(&breaker, &clock, 5);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn slow_call_threshold_needs_a_failure_rate_threshold() {
+        let clock = ManualClock::new();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_slow_call_threshold(Duration::from_secs(1))
+            .with_clock(clock.clone());
+
+        for _ in 0..5 {
+            timed_call(&breaker, &clock, 10);
+        }
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn failure_rate_trips_without_consecutive_failures() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(3)
+            .with_failure_rate_threshold(60.0, SlidingWindow::CountBased(5))
+            .with_minimum_calls(5);
+
+        for succeed in [false, true, false, true] {
+            call(&breaker, succeed);
+        }
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        // 3 of 5 failed (60%), never 3 in a row
+        call(&breaker, false);
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    fn half_open_breaker(clock: &ManualClock) -> CircuitBreakerMiddleware {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_success_threshold(2)
+            .with_half_open_max_calls(1)
+            .with_timeout(Duration::from_secs(30))
+            .with_clock(clock.clone());
+        call(&breaker, false);
+        clock.advance(Duration::from_secs(30));
+        breaker
+    }
+
+    #[test]
+    fn half_open_max_calls_limits_trial_calls_in_flight() {
+        let clock = ManualClock::new();
+        let breaker = half_open_breaker(&clock);
+
+        let mut concurrent = None;
+        let trial = breaker.execute(&Call, &mut EventContext::new(), &mut |_| {
+            concurrent = Some(call(&breaker, true));
+            EventResult::Success(())
+        });
+        assert!(trial.is_success());
+        assert!(matches!(concurrent, Some(EventResult::Failure(ref e)) if e.contains("HALF-OPEN")));
+        assert_eq!(breaker.get_state(), CircuitState::HalfOpen);
+
+        // The first trial freed its slot, so a second one is admitted and closes the circuit
+        assert!(call(&breaker, true).is_success());
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn panicking_trial_call_frees_its_slot() {
+        let clock = ManualClock::new();
+        let breaker = half_open_breaker(&clock);
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            breaker.execute(&Call, &mut EventContext::new(), &mut |_| panic!("event failed"))
+        }));
+        assert!(panicked.is_err());
+
+        assert!(call(&breaker, true).is_success());
+        assert_eq!(breaker.get_state(), CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn state_change_callbacks_see_every_transition() {
+        let clock = ManualClock::new();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_success_threshold(1)
+            .with_timeout(Duration::from_secs(30))
+            .with_clock(clock.clone())
+            .on_state_change(move |change| seen.lock().unwrap().push(change.clone()));
+
+        call(&breaker, false);
+        clock.advance(Duration::from_secs(30));
+        call(&breaker, true);
+
+        let changes = changes.lock().unwrap();
+        let transitions: Vec<_> = changes.iter().map(|c| (c.from, c.to)).collect();
+        assert_eq!(
+            transitions,
+            [
+                (CircuitState::Closed, CircuitState::Open),
+                (CircuitState::Open, CircuitState::HalfOpen),
+                (CircuitState::HalfOpen, CircuitState::Closed),
+            ]
+        );
+        assert!(changes.iter().all(|c| c.circuit == GLOBAL_CIRCUIT && c.event_name == "Call"));
+
+        let epoch_secs = |time: std::time::SystemTime| {
+            time.duration_since(std::time::UNIX_EPOCH).unwrap().as_secs()
+        };
+        assert_eq!(changes[2].timestamp, epoch_secs(clock.system_time()));
+        assert_eq!(changes[2].timestamp - changes[0].timestamp, 30);
+    }
+
+    struct Named(&'static str);
+
+    impl ChainableEvent for Named {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    fn fail(breaker: &CircuitBreakerMiddleware, event: &Named, tenant: Option<&str>) {
+        let mut context = EventContext::new();
+        if let Some(tenant) = tenant {
+            context.set("tenant_id", tenant.to_string());
+        }
+        breaker.execute(event, &mut context, &mut |_| EventResult::Failure("down".to_string()));
+    }
+
+    #[test]
+    fn per_event_scope_keeps_a_circuit_per_event() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_scope(CircuitScope::PerEvent);
+
+        fail(&breaker, &Named("ChargeCard"), None);
+
+        let registry = breaker.registry();
+        assert_eq!(registry.get_state("event:ChargeCard"), Some(CircuitState::Open));
+        assert_eq!(registry.get_state("event:SendEmail"), None);
+        let mut context = EventContext::new();
+        let result = breaker.execute(&Named("SendEmail"), &mut context, &mut |_| {
+            EventResult::Success(())
+        });
+        assert!(result.is_success());
+        assert_eq!(registry.get_state("event:SendEmail"), Some(CircuitState::Closed));
+    }
+
+    #[test]
+    fn context_key_scope_never_shares_a_circuit_with_an_event() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_scope(CircuitScope::ContextKey("tenant_id".to_string()));
+
+        // A tenant named like an event, and an event without the context key
+        fail(&breaker, &Named("Call"), Some("ChargeCard"));
+        fail(&breaker, &Named("ChargeCard"), None);
+
+        let registry = breaker.registry();
+        assert_eq!(
+            registry.states(),
+            [
+                ("ctx:tenant_id=ChargeCard".to_string(), CircuitState::Open),
+                ("event:ChargeCard".to_string(), CircuitState::Open),
+            ]
+        );
+
+        assert!(registry.reset("ctx:tenant_id=ChargeCard"));
+        assert_eq!(registry.get_state("ctx:tenant_id=ChargeCard"), Some(CircuitState::Closed));
+        assert_eq!(registry.get_state("event:ChargeCard"), Some(CircuitState::Open));
+    }
+
+    #[test]
+    fn breakers_sharing_a_registry_share_circuits() {
+        let registry = CircuitRegistry::new();
+        let first = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_scope(CircuitScope::PerEvent)
+            .with_registry(registry.clone());
+        let second = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_scope(CircuitScope::PerEvent)
+            .with_registry(registry.clone());
+
+        fail(&first, &Named("ChargeCard"), None);
+
+        let mut context = EventContext::new();
+        let result = second.execute(&Named("ChargeCard"), &mut context, &mut |_| {
+            EventResult::Success(())
+        });
+        assert!(matches!(result, EventResult::Failure(ref e) if e.contains("OPEN")));
+
+        registry.reset_all();
+        assert_eq!(second.get_state(), CircuitState::Closed);
+    }
+
+    fn respond(breaker: &CircuitBreakerMiddleware, result: EventResult<()>) -> EventResult<()> {
+        let mut result = Some(result);
+        breaker.execute(&Call, &mut EventContext::new(), &mut |_| result.take().unwrap())
+    }
+
+    fn failure_count(breaker: &CircuitBreakerMiddleware) -> u32 {
+        let circuits = breaker.registry.circuits.lock().unwrap();
+        circuits[GLOBAL_CIRCUIT].failure_count
+    }
+
+    #[test]
+    fn ignored_failures_leave_the_failure_count_unchanged() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(2)
+            .with_failure_classifier(FailureClassifier::MiddlewareFailuresOnly);
+
+        respond(&breaker, EventResult::MiddlewareFailure("timeout".to_string()));
+        assert_eq!(failure_count(&breaker), 1);
+
+        for _ in 0..5 {
+            let result = respond(&breaker, EventResult::Failure("wrong password".to_string()));
+            assert!(matches!(result, EventResult::Failure(ref e) if e == "wrong password"));
+        }
+        assert_eq!(failure_count(&breaker), 1);
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        respond(&breaker, EventResult::MiddlewareFailure("timeout".to_string()));
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn custom_classified_failure_trips_the_circuit() {
+        let breaker = CircuitBreakerMiddleware::new()
+            .with_logging(false)
+            .with_failure_threshold(1)
+            .with_failure_classifier(FailureClassifier::custom(|result| {
+                result.get_error().is_some_and(|err| err.starts_with("HTTP 5"))
+            }));
+
+        respond(&breaker, EventResult::Failure("HTTP 404".to_string()));
+        assert_eq!(breaker.get_state(), CircuitState::Closed);
+
+        respond(&breaker, EventResult::Failure("HTTP 503".to_string()));
+        assert_eq!(breaker.get_state(), CircuitState::Open);
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of time for time-dependent middleware
+///
+/// Retry backoff, rate limiting, bulkhead queue timeouts, circuit breaker
+/// timeouts, timing, metrics windows and chaos latency all read the time and
+/// sleep through a `Clock`. Production code uses [`SystemClock`] (the default
+/// everywhere); tests can pass a [`ManualClock`] and advance it explicitly,
+/// so a 30-second circuit timeout takes no real time at all.
+pub trait Clock: Send + Sync {
+    /// Current point in time
+    fn now(&self) -> Instant;
+
+    /// Block for `duration`
+    fn sleep(&self, duration: Duration);
+
+    /// Current wall-clock time, for timestamps in reports
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Shared handle to a clock, as stored by the middleware
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real clock: `Instant::now()` and `std::thread::sleep`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Shared handle to the system clock
+    pub fn shared() -> SharedClock {
+        Arc::new(SystemClock)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when told to
+///
+/// `sleep` returns immediately and advances the clock by the requested
+/// duration, so code that sleeps (retry backoff, blocking rate limits, chaos
+/// latency) observes the time passing without waiting. Clones share the same
+/// time.
+///
+/// # Example
+///
+/// ```ignore
+/// use event_chains::middleware::clock::ManualClock;
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let breaker = CircuitBreakerMiddleware::new()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_clock(clock.clone());
+///
+/// // ... trip the breaker ...
+/// clock.advance(Duration::from_secs(30));
+/// // the next call is a half-open trial
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    origin: Instant,
+    origin_system_time: SystemTime,
+    offset: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    /// Create a clock frozen at the current instant
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            origin_system_time: SystemTime::now(),
+            offset: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut offset) = self.offset.lock() {
+            *offset += duration;
+        }
+    }
+
+    /// Total time the clock has been advanced since creation
+    pub fn elapsed(&self) -> Duration {
+        self.offset.lock().map(|offset| *offset).unwrap_or_default()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.origin_system_time + self.elapsed()
+    }
+}
@@ -0,0 +1,192 @@
+//! Internal output for the built-in middleware
+//!
+//! Without the `log` or `tracing` features, messages are printed to stdout as
+//! before. With `tracing` enabled they become tracing events (target
+//! `event_chains`) with `component` and `event` fields; with only `log`
+//! enabled they are emitted as log records with target
+//! `event_chains::{component}`.
+
+use crate::middleware::logging::LogLevel;
+
+/// Emit a diagnostic message from a built-in middleware
+///
+/// * `component` - Short middleware name, e.g. `"retry"`
+/// * `event_name` - Event being executed
+/// * `message` - Human-readable message without leading indentation, passed
+///   verbatim to every backend
+pub(crate) fn emit(level: LogLevel, component: &'static str, event_name: &str, message: &str) {
+    #[cfg(feature = "tracing")]
+    {
+        match level {
+            LogLevel::Debug => {
+                tracing::debug!(target: "event_chains", component, event = event_name, message)
+            }
+            LogLevel::Info => {
+                tracing::info!(target: "event_chains", component, event = event_name, message)
+            }
+            LogLevel::Warn => {
+                tracing::warn!(target: "event_chains", component, event = event_name, message)
+            }
+            LogLevel::Error => {
+                tracing::error!(target: "event_chains", component, event = event_name, message)
+            }
+        }
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    {
+        let level = match level {
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        };
+        let target = format!("event_chains::{}", component);
+        log::log!(target: &target, level, "[{}] {}", event_name, message);
+    }
+
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    {
+        let _ = (level, component, event_name);
+        println!("{}", message);
+    }
+}
+
+#[cfg(all(test, any(feature = "log", feature = "tracing")))]
+mod tests {
+    use crate::core::event_context::EventContext;
+    use crate::core::event_result::EventResult;
+    use crate::events::chainable_event::ChainableEvent;
+    use crate::events::event_middleware::EventMiddleware;
+    use crate::middleware::chaos::{ChaosConfig, ChaosMiddleware, ChaosType};
+    use crate::middleware::rate_limit::{RateLimitMiddleware, RateLimitStrategy};
+
+    struct Named(&'static str);
+
+    impl ChainableEvent for Named {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    /// Emit a chaos skip and a rate limit rejection for `event`
+    fn emit_from_middleware(event: &Named) {
+        let chaos = ChaosMiddleware::with_config(ChaosConfig {
+            probability: 1.0,
+            chaos_types: vec![ChaosType::Skip],
+            seed: Some(1),
+            ..Default::default()
+        });
+        chaos.execute(event, &mut EventContext::new(), &mut |_| EventResult::Success(()));
+
+        let rate_limit = RateLimitMiddleware::with_burst(1, 1, RateLimitStrategy::Block);
+        for _ in 0..2 {
+            rate_limit.execute(event, &mut EventContext::new(), &mut |_| EventResult::Success(()));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_events_carry_clean_messages_and_fields() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records every event as `target component event message`
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        #[derive(Default)]
+        struct Fields(Vec<String>);
+
+        impl Visit for Fields {
+            fn record_str(&mut self, _field: &Field, value: &str) {
+                self.0.push(value.to_string());
+            }
+
+            fn record_debug(&mut self, _field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push(format!("{:?}", value));
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _span: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                let line = format!("{} {}", event.metadata().target(), fields.0.join(" "));
+                self.0.lock().unwrap().push(line);
+            }
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            emit_from_middleware(&Named("Call"));
+        });
+
+        let lines = capture.0.lock().unwrap();
+        assert_eq!(lines[0], "event_chains chaos Call [CHAOS] Skipping execution of Call");
+        assert!(lines[1].starts_with("event_chains rate_limit Call Rate limit exceeded for Call."));
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    #[test]
+    fn log_records_carry_clean_messages() {
+        use std::sync::Mutex;
+
+        static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        struct Capture;
+
+        impl log::Log for Capture {
+            fn enabled(&self, _metadata: &log::Metadata<'_>) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record<'_>) {
+                let line = format!("{} {}", record.target(), record.args());
+                LINES.lock().unwrap().push(line);
+            }
+
+            fn flush(&self) {}
+        }
+
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        emit_from_middleware(&Named("LogCapture"));
+
+        // Other tests may log concurrently; keep only this test's records
+        let lines: Vec<_> = LINES
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|line| line.contains("[LogCapture]"))
+            .cloned()
+            .collect();
+        assert_eq!(
+            lines[0],
+            "event_chains::chaos [LogCapture] [CHAOS] Skipping execution of LogCapture"
+        );
+        assert!(lines[1].starts_with("event_chains::rate_limit [LogCapture] Rate limit exceeded"));
+    }
+}
@@ -0,0 +1,736 @@
+use crate::core::chain_result::ChainResult;
+use crate::core::event_chain::EventChain;
+use crate::core::event_context::EventContext;
+use crate::middleware::fuzz_corpus::FuzzValue;
+use crate::middleware::fuzzing::FuzzInput;
+use crate::middleware::pattern::glob_match;
+use crate::middleware::rng::{SeededRng, random_seed};
+use std::cell::Cell;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::OnceLock;
+
+/// Tokens inserted into strings by [`Mutation::InsertToken`]
+const TOKENS: &[&str] = &[
+    "'", "\"", "<", ">", "&", ";", "\\", "../", "%s%n", "\0", "\r\n", "\u{202e}", "${", "{{",
+    "-1", "9223372036854775808",
+];
+
+/// Replacement strings used by [`Mutation::BoundaryNumber`]
+const BOUNDARY_NUMBERS: &[&str] = &[
+    "0",
+    "-0",
+    "-1",
+    "2147483647",
+    "2147483648",
+    "-2147483649",
+    "9223372036854775807",
+    "18446744073709551616",
+    "1e309",
+    "NaN",
+];
+
+thread_local! {
+    /// Number of live [`QuietPanics`] guards on this thread
+    static QUIET_PANICS: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Set once the quiet panic hook has been installed
+static QUIET_HOOK: OnceLock<()> = OnceLock::new();
+
+/// Silences panic messages on the current thread while alive
+///
+/// The first guard installs one process-wide hook that forwards to the
+/// previous hook unless a guard is live on the panicking thread. The hook is
+/// never replaced afterwards, so guards on other threads (or a hook set by
+/// the caller before the first campaign) are not disturbed.
+struct QuietPanics;
+
+impl QuietPanics {
+    fn install() -> Self {
+        QUIET_HOOK.get_or_init(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                if QUIET_PANICS.with(Cell::get) == 0 {
+                    previous(info);
+                }
+            }));
+        });
+        QUIET_PANICS.with(|quiet| quiet.set(quiet.get() + 1));
+        QuietPanics
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET_PANICS.with(|quiet| quiet.set(quiet.get() - 1));
+    }
+}
+
+/// Ways a campaign mutates string and byte values
+///
+/// Numbers always get boundary values or bit flips, booleans are flipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Flip one bit of one byte
+    BitFlip,
+    /// Cut the value at a random position
+    Truncate,
+    /// Join the start of the value with the end of another corpus entry
+    Splice,
+    /// Insert a special token such as a quote, `../` or a bidi control
+    InsertToken,
+    /// Repeat a random chunk of the value
+    Duplicate,
+    /// Remove a random range of bytes
+    DeleteRange,
+    /// Replace the value with a boundary number such as `2147483648`
+    BoundaryNumber,
+}
+
+impl Mutation {
+    /// All mutations
+    pub fn all() -> Vec<Mutation> {
+        vec![
+            Mutation::BitFlip,
+            Mutation::Truncate,
+            Mutation::Splice,
+            Mutation::InsertToken,
+            Mutation::Duplicate,
+            Mutation::DeleteRange,
+            Mutation::BoundaryNumber,
+        ]
+    }
+}
+
+/// A crashing input found by a campaign, shrunk to a minimal reproducer
+#[derive(Debug, Clone)]
+pub struct CampaignCrash {
+    /// Panic message, or the outcome matched by the crash condition
+    pub message: String,
+    /// Iteration that first produced the crash (0 is the unmodified seed)
+    pub iteration: u64,
+    /// Minimized input reproducing the crash
+    pub inputs: Vec<FuzzInput>,
+    /// Input as originally generated
+    pub original_inputs: Vec<FuzzInput>,
+}
+
+impl CampaignCrash {
+    /// Write the minimized input into `context`, to replay the crash
+    pub fn apply(&self, context: &mut EventContext) {
+        for input in &self.inputs {
+            input.value.apply(context, &input.key);
+        }
+    }
+}
+
+/// Outcome of a fuzz campaign
+#[derive(Debug, Clone)]
+pub struct CampaignReport {
+    /// Seed of the campaign; pass it to [`FuzzCampaign::with_seed`] to rerun it
+    pub seed: u64,
+    pub iterations: u64,
+    /// Chain executions, including those spent on shrinking
+    pub executions: u64,
+    /// Distinct outcomes, in the order they were discovered
+    pub outcomes: Vec<String>,
+    /// Inputs that produced a new outcome (the first entry is the seed)
+    pub corpus: Vec<Vec<FuzzInput>>,
+    /// One entry per distinct crash
+    pub crashes: Vec<CampaignCrash>,
+}
+
+impl CampaignReport {
+    /// Whether any crash was found
+    pub fn has_crashes(&self) -> bool {
+        !self.crashes.is_empty()
+    }
+
+    /// Print the report to stdout
+    pub fn print(&self) {
+        println!("\n=== Fuzz Campaign ===");
+        println!("Seed:                            {}", self.seed);
+        println!("Iterations:                      {}", self.iterations);
+        println!("Executions:                      {}", self.executions);
+        println!("Distinct outcomes:               {}", self.outcomes.len());
+        println!("Corpus size:                     {}", self.corpus.len());
+        println!("Crashes:                         {}", self.crashes.len());
+        for crash in &self.crashes {
+            println!("  - {} (iteration {})", crash.message, crash.iteration);
+            for input in &crash.inputs {
+                println!("      {} = {:?}", input.key, input.value);
+            }
+        }
+        println!();
+    }
+}
+
+/// Predicate marking a chain result as a crash
+pub type CrashCondition = Box<dyn Fn(&ChainResult) -> bool>;
+
+/// Result of one chain execution
+enum Execution {
+    Completed(ChainResult),
+    Panicked(String),
+}
+
+/// Mutation-based, coverage-free fuzzing of a whole [`EventChain`]
+///
+/// Starting from a seed context, the campaign repeatedly picks an input
+/// from its corpus, mutates one to three of its values and executes the
+/// chain on a copy of the seed context with the mutated values. Inputs that
+/// produce a new outcome (a different set of failing events and error
+/// messages) are added to the corpus, so later mutations build on them.
+///
+/// Inputs that panic, or match the condition set with
+/// [`with_crash_condition`](Self::with_crash_condition), are crashes. Each
+/// distinct crash is shrunk to a minimal reproducer by reverting values to
+/// their seed and deleting bytes while the crash persists.
+///
+/// Mutated keys hold `String`, `Vec<u8>`, numbers or `bool`; other values
+/// are left alone. Runs are reproducible for a given seed as long as the
+/// chain itself is deterministic (seed any chaos or fuzzing middleware too).
+///
+/// # Example
+///
+/// ```ignore
+/// let chain = EventChain::new()
+///     .event(ParseHeader)
+///     .event(StoreRecord)
+///     .with_fault_tolerance(FaultToleranceMode::Strict);
+///
+/// let mut seed = EventContext::new();
+/// seed.set("header", "Content-Length: 42".to_string());
+/// seed.set("body", b"hello".to_vec());
+///
+/// let report = FuzzCampaign::new()
+///     .with_seed(7)
+///     .with_iterations(10_000)
+///     .run(&chain, &seed);
+///
+/// report.print();
+/// for crash in &report.crashes {
+///     let mut context = seed.clone();
+///     crash.apply(&mut context);
+///     // ... turn into a regression test ...
+/// }
+/// ```
+pub struct FuzzCampaign {
+    seed: Option<u64>,
+    iterations: u64,
+    key_patterns: Vec<String>,
+    mutations: Vec<Mutation>,
+    max_len: usize,
+    max_corpus: usize,
+    shrink: bool,
+    max_shrink_runs: u64,
+    crash_condition: Option<CrashCondition>,
+    quiet_panics: bool,
+}
+
+impl FuzzCampaign {
+    /// Create a campaign of 1000 iterations over every supported key
+    pub fn new() -> Self {
+        Self {
+            seed: None,
+            iterations: 1000,
+            key_patterns: vec!["*".to_string()],
+            mutations: Mutation::all(),
+            max_len: 4096,
+            max_corpus: 256,
+            shrink: true,
+            max_shrink_runs: 500,
+            crash_condition: None,
+            quiet_panics: true,
+        }
+    }
+
+    /// Use a fixed seed, e.g. the one recorded in [`CampaignReport::seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Number of mutated inputs to execute
+    pub fn with_iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Only mutate keys matching one of the glob patterns
+    pub fn with_keys(mut self, patterns: &[&str]) -> Self {
+        self.key_patterns = patterns.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Mutations to apply to string and byte values
+    pub fn with_mutations(mut self, mutations: Vec<Mutation>) -> Self {
+        self.mutations = mutations;
+        self
+    }
+
+    /// Maximum length of mutated strings and byte buffers (default 4096)
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Maximum number of inputs kept in the corpus (default 256)
+    pub fn with_max_corpus(mut self, max_corpus: usize) -> Self {
+        self.max_corpus = max_corpus.max(1);
+        self
+    }
+
+    /// Shrink crashing inputs (default), using at most `max_runs` executions each
+    pub fn with_shrinking(mut self, enabled: bool, max_runs: u64) -> Self {
+        self.shrink = enabled;
+        self.max_shrink_runs = max_runs;
+        self
+    }
+
+    /// Also treat results matching `condition` as crashes, e.g.
+    /// `|result| result.failures.iter().any(|f| f.is_middleware_failure)`
+    pub fn with_crash_condition<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&ChainResult) -> bool + 'static,
+    {
+        self.crash_condition = Some(Box::new(condition));
+        self
+    }
+
+    /// Silence panic messages while the campaign runs (default `true`)
+    ///
+    /// Only panics on the thread running the campaign are silenced.
+    pub fn with_quiet_panics(mut self, quiet: bool) -> Self {
+        self.quiet_panics = quiet;
+        self
+    }
+
+    /// Fuzz `chain`, starting from the values in `seed_context`
+    pub fn run(&self, chain: &EventChain, seed_context: &EventContext) -> CampaignReport {
+        let seed = self.seed.unwrap_or_else(random_seed);
+        let mut rng = SeededRng::new(seed);
+        let mut report = CampaignReport {
+            seed,
+            iterations: 0,
+            executions: 0,
+            outcomes: Vec::new(),
+            corpus: Vec::new(),
+            crashes: Vec::new(),
+        };
+
+        let seed_inputs = self.seed_inputs(seed_context);
+        if seed_inputs.is_empty() {
+            return report;
+        }
+
+        let _quiet = self.quiet_panics.then(QuietPanics::install);
+
+        self.record(chain, seed_context, &seed_inputs, &seed_inputs, 0, &mut report);
+        if report.corpus.is_empty() {
+            // The seed input itself crashed: there is nothing to mutate
+            return report;
+        }
+        for iteration in 1..=self.iterations {
+            report.iterations = iteration;
+            let parent = &report.corpus[rng.next_index(report.corpus.len())];
+            let mut input = parent.clone();
+            for _ in 0..1 + rng.next_index(3) {
+                let idx = rng.next_index(input.len());
+                input[idx].value = self.mutate(&input[idx], &report.corpus, &mut rng);
+            }
+            self.record(chain, seed_context, &seed_inputs, &input, iteration, &mut report);
+        }
+
+        report
+    }
+
+    /// Values of the keys to mutate, sorted by key so runs are reproducible
+    fn seed_inputs(&self, context: &EventContext) -> Vec<FuzzInput> {
+        let mut keys: Vec<&str> = context
+            .keys()
+            .filter(|key| self.key_patterns.iter().any(|pattern| glob_match(pattern, key)))
+            .collect();
+        keys.sort_unstable();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let value = FuzzValue::from_stored(context.get_raw(key)?)?;
+                Some(FuzzInput {
+                    key: key.to_string(),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Execute one input and add it to the corpus or the crashes if it is new
+    fn record(
+        &self,
+        chain: &EventChain,
+        seed_context: &EventContext,
+        seed_inputs: &[FuzzInput],
+        input: &[FuzzInput],
+        iteration: u64,
+        report: &mut CampaignReport,
+    ) {
+        let execution = self.execute(chain, seed_context, input);
+        report.executions += 1;
+        let signature = signature(&execution, input);
+        if report.outcomes.contains(&signature) {
+            return;
+        }
+        report.outcomes.push(signature.clone());
+
+        match self.crash_message(&execution, &signature) {
+            Some(message) => {
+                let minimized = if self.shrink {
+                    self.shrink(chain, seed_context, seed_inputs, input, &signature, report)
+                } else {
+                    input.to_vec()
+                };
+                report.crashes.push(CampaignCrash {
+                    message,
+                    iteration,
+                    inputs: minimized,
+                    original_inputs: input.to_vec(),
+                });
+            }
+            None if report.corpus.len() < self.max_corpus => report.corpus.push(input.to_vec()),
+            None => {}
+        }
+    }
+
+    fn execute(
+        &self,
+        chain: &EventChain,
+        seed_context: &EventContext,
+        input: &[FuzzInput],
+    ) -> Execution {
+        let mut context = seed_context.clone();
+        for value in input {
+            value.value.apply(&mut context, &value.key);
+        }
+
+        match catch_unwind(AssertUnwindSafe(|| chain.execute(&mut context))) {
+            Ok(result) => Execution::Completed(result),
+            Err(panic) => Execution::Panicked(
+                panic
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "<non-string panic payload>".to_string()),
+            ),
+        }
+    }
+
+    fn crash_message(&self, execution: &Execution, signature: &str) -> Option<String> {
+        match execution {
+            Execution::Panicked(message) => Some(format!("panic: {}", message)),
+            Execution::Completed(result) => self
+                .crash_condition
+                .as_ref()
+                .is_some_and(|condition| condition(result))
+                .then(|| signature.to_string()),
+        }
+    }
+
+    /// Shrink a crashing input while it keeps producing the same outcome
+    fn shrink(
+        &self,
+        chain: &EventChain,
+        seed_context: &EventContext,
+        seed_inputs: &[FuzzInput],
+        input: &[FuzzInput],
+        crash_signature: &str,
+        report: &mut CampaignReport,
+    ) -> Vec<FuzzInput> {
+        let mut runs = 0;
+        let mut still_crashes = |candidate: &[FuzzInput], report: &mut CampaignReport| {
+            if runs >= self.max_shrink_runs {
+                return false;
+            }
+            runs += 1;
+            report.executions += 1;
+            signature(&self.execute(chain, seed_context, candidate), candidate) == crash_signature
+        };
+
+        let mut current = input.to_vec();
+
+        // Revert values the crash does not depend on
+        for (idx, original) in seed_inputs.iter().enumerate() {
+            if current[idx].value == original.value {
+                continue;
+            }
+            let mut candidate = current.clone();
+            candidate[idx].value = original.value.clone();
+            if still_crashes(&candidate, report) {
+                current = candidate;
+            }
+        }
+
+        // Delete ever smaller chunks of the remaining strings and bytes
+        for idx in 0..current.len() {
+            let Some(mut bytes) = as_bytes(&current[idx].value) else {
+                continue;
+            };
+            let mut chunk = bytes.len().div_ceil(2);
+            while chunk > 0 {
+                let mut start = 0;
+                while start < bytes.len() {
+                    let mut shorter = bytes.clone();
+                    shorter.drain(start..(start + chunk).min(bytes.len()));
+                    let mut candidate = current.clone();
+                    candidate[idx].value = with_bytes(&current[idx].value, shorter.clone());
+                    if still_crashes(&candidate, report) {
+                        bytes = shorter;
+                        current = candidate;
+                    } else {
+                        start += chunk;
+                    }
+                }
+                chunk /= 2;
+            }
+        }
+
+        current
+    }
+
+    /// Produce a mutated copy of `input`'s value
+    fn mutate(&self, input: &FuzzInput, corpus: &[Vec<FuzzInput>], rng: &mut SeededRng) -> FuzzValue {
+        let value = &input.value;
+        let Some(mut bytes) = as_bytes(value) else {
+            return match value {
+                FuzzValue::Bool(flag) => FuzzValue::Bool(!flag),
+                FuzzValue::I64(n) if rng.next_u64() & 1 == 1 => {
+                    FuzzValue::I64(n ^ (1 << rng.next_index(64)))
+                }
+                FuzzValue::U64(n) if rng.next_u64() & 1 == 1 => {
+                    FuzzValue::U64(n ^ (1 << rng.next_index(64)))
+                }
+                FuzzValue::I32(n) if rng.next_u64() & 1 == 1 => {
+                    FuzzValue::I32(n ^ (1 << rng.next_index(32)))
+                }
+                FuzzValue::U32(n) if rng.next_u64() & 1 == 1 => {
+                    FuzzValue::U32(n ^ (1 << rng.next_index(32)))
+                }
+                _ => match value.kind() {
+                    Some(kind) => kind.hostile(rng, ""),
+                    None => value.clone(),
+                },
+            };
+        };
+
+        if self.mutations.is_empty() {
+            return value.clone();
+        }
+        let mutation = self.mutations[rng.next_index(self.mutations.len())];
+        match mutation {
+            Mutation::BitFlip => {
+                if !bytes.is_empty() {
+                    let idx = rng.next_index(bytes.len());
+                    bytes[idx] ^= 1 << rng.next_index(8);
+                }
+            }
+            Mutation::Truncate => {
+                if !bytes.is_empty() {
+                    bytes.truncate(rng.next_index(bytes.len()));
+                }
+            }
+            Mutation::Splice => {
+                let donor = &corpus[rng.next_index(corpus.len())];
+                let other = donor
+                    .iter()
+                    .find(|other| other.key == input.key)
+                    .and_then(|other| as_bytes(&other.value))
+                    .unwrap_or_default();
+                let head = rng.next_index(bytes.len() + 1);
+                let tail = rng.next_index(other.len() + 1);
+                bytes.truncate(head);
+                bytes.extend_from_slice(&other[tail..]);
+            }
+            Mutation::InsertToken => {
+                let token = TOKENS[rng.next_index(TOKENS.len())];
+                let at = rng.next_index(bytes.len() + 1);
+                bytes.splice(at..at, token.bytes());
+            }
+            Mutation::Duplicate => {
+                if !bytes.is_empty() {
+                    let start = rng.next_index(bytes.len());
+                    let end = start + 1 + rng.next_index(bytes.len() - start);
+                    let chunk = bytes[start..end].to_vec();
+                    for _ in 0..1 + rng.next_index(8) {
+                        bytes.splice(end..end, chunk.iter().copied());
+                    }
+                }
+            }
+            Mutation::DeleteRange => {
+                if !bytes.is_empty() {
+                    let start = rng.next_index(bytes.len());
+                    let end = start + 1 + rng.next_index(bytes.len() - start);
+                    bytes.drain(start..end);
+                }
+            }
+            Mutation::BoundaryNumber => {
+                bytes = BOUNDARY_NUMBERS[rng.next_index(BOUNDARY_NUMBERS.len())]
+                    .as_bytes()
+                    .to_vec();
+            }
+        }
+        bytes.truncate(self.max_len);
+        with_bytes(value, bytes)
+    }
+}
+
+impl Default for FuzzCampaign {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bytes of a string or byte value
+fn as_bytes(value: &FuzzValue) -> Option<Vec<u8>> {
+    match value {
+        FuzzValue::Text(text) => Some(text.as_bytes().to_vec()),
+        FuzzValue::Bytes(bytes) => Some(bytes.clone()),
+        _ => None,
+    }
+}
+
+/// Value of the same kind as `like` holding `bytes` (lossily decoded for strings)
+fn with_bytes(like: &FuzzValue, bytes: Vec<u8>) -> FuzzValue {
+    match like {
+        FuzzValue::Text(_) => FuzzValue::Text(String::from_utf8_lossy(&bytes).into_owned()),
+        _ => FuzzValue::Bytes(bytes),
+    }
+}
+
+/// Outcome of an execution, normalized so that messages differing only in
+/// the offending input, lengths or offsets count as the same outcome: echoed
+/// input values and quoted text become `…`, digit runs become `N`
+fn signature(execution: &Execution, input: &[FuzzInput]) -> String {
+    let mut raw = match execution {
+        Execution::Panicked(message) => format!("panic: {}", message),
+        Execution::Completed(result) => {
+            let failures: Vec<String> = result
+                .failures
+                .iter()
+                .map(|f| format!("{}: {}", f.event_name, f.error_message))
+                .collect();
+            format!("{:?} [{}]", result.status, failures.join(" | "))
+        }
+    };
+
+    // Longest first, so a value containing another is replaced whole. Very
+    // short values are kept, they would match ordinary words.
+    let mut echoed: Vec<String> = input
+        .iter()
+        .filter_map(|input| input.value.as_text())
+        .filter(|text| text.len() >= 3)
+        .flat_map(|text| [format!("{:?}", text), text.to_string()])
+        .collect();
+    echoed.sort_by_key(|text| std::cmp::Reverse(text.len()));
+    for text in echoed {
+        raw = raw.replace(&text, "…");
+    }
+
+    let mut normalized = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                normalized.push(c);
+                normalized.push('…');
+                if chars.by_ref().any(|next| next == c) {
+                    normalized.push(c);
+                }
+            }
+            '0'..='9' if normalized.ends_with('N') => {}
+            '0'..='9' => normalized.push('N'),
+            _ => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_result::EventResult;
+    use crate::events::chainable_event::ChainableEvent;
+
+    /// Panics on input containing a quote
+    struct ParseQuoted;
+
+    impl ChainableEvent for ParseQuoted {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            let input: String = context.get("input").unwrap_or_default();
+            if input.contains('\'') {
+                panic!("unbalanced quote");
+            }
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "ParseQuoted"
+        }
+    }
+
+    fn seed(input: &str) -> EventContext {
+        let mut context = EventContext::new();
+        context.set("input", input.to_string());
+        context
+    }
+
+    #[test]
+    fn crashing_seed_is_reported_without_mutating() {
+        let chain = EventChain::new().event(ParseQuoted);
+        let report = FuzzCampaign::new().with_seed(1).run(&chain, &seed("it's"));
+
+        assert_eq!(report.iterations, 0);
+        assert!(report.corpus.is_empty());
+        assert_eq!(report.crashes.len(), 1);
+        assert_eq!(report.crashes[0].iteration, 0);
+    }
+
+    #[test]
+    fn crashes_are_shrunk_to_a_minimal_input() {
+        let chain = EventChain::new().event(ParseQuoted);
+        let report = FuzzCampaign::new()
+            .with_seed(7)
+            .with_iterations(500)
+            .run(&chain, &seed("hello world"));
+
+        let crash = report.crashes.first().expect("campaign should find the quote crash");
+        assert!(crash.message.contains("unbalanced quote"));
+        assert_eq!(crash.inputs[0].value, FuzzValue::Text("'".to_string()));
+    }
+
+    #[test]
+    fn campaigns_are_reproducible_for_a_seed() {
+        let chain = EventChain::new().event(ParseQuoted);
+        let run = || {
+            FuzzCampaign::new()
+                .with_seed(3)
+                .with_iterations(200)
+                .run(&chain, &seed("hello world"))
+        };
+
+        let (first, second) = (run(), run());
+        assert_eq!(first.outcomes, second.outcomes);
+        assert_eq!(first.executions, second.executions);
+    }
+
+    #[test]
+    fn quiet_panic_guards_only_count_live_guards_on_their_thread() {
+        let outer = QuietPanics::install();
+        let inner = QuietPanics::install();
+        assert_eq!(QUIET_PANICS.with(Cell::get), 2);
+        assert_eq!(std::thread::spawn(|| QUIET_PANICS.with(Cell::get)).join().unwrap(), 0);
+
+        drop(inner);
+        drop(outer);
+        assert_eq!(QUIET_PANICS.with(Cell::get), 0);
+        assert!(QUIET_HOOK.get().is_some());
+    }
+}
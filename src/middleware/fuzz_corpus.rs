@@ -0,0 +1,417 @@
+use crate::core::event_context::EventContext;
+use crate::middleware::rng::SeededRng;
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A named list of user-supplied string payloads
+///
+/// Injected when [`FuzzType::Corpus`](crate::middleware::fuzzing::FuzzType::Corpus)
+/// is selected. Useful for payload lists maintained outside the code, such
+/// as wordlists of known attack strings or inputs from past incidents.
+///
+/// # Example
+///
+/// ```ignore
+/// let fuzzer = FuzzingMiddleware::new(0.3)
+///     .with_corpus(FuzzCorpus::from_file("fuzz/sqli.txt")?)
+///     .with_corpus(FuzzCorpus::new("legacy ids", vec!["0000".into(), "-0".into()]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzCorpus {
+    name: String,
+    payloads: Vec<String>,
+}
+
+impl FuzzCorpus {
+    pub fn new(name: &str, payloads: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            payloads,
+        }
+    }
+
+    /// Load one payload per line, named after the file stem
+    ///
+    /// Empty lines and lines starting with `#` are skipped; trailing `\r`
+    /// is removed, other whitespace is kept as part of the payload.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let payloads = fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(Self { name, payloads })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn payloads(&self) -> &[String] {
+        &self.payloads
+    }
+
+    pub fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+}
+
+/// Type of value generated for a context key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// `String` payloads from the selected fuzz type
+    Text,
+    I64,
+    I32,
+    U64,
+    U32,
+    Usize,
+    F64,
+    F32,
+    Bool,
+    /// `Vec<u8>`
+    Bytes,
+}
+
+impl ValueKind {
+    /// Kind of a stored value, if it is one of the supported primitive types
+    pub(crate) fn of(value: &(dyn Any + Send + Sync)) -> Option<Self> {
+        let type_id = value.type_id();
+        let kinds = [
+            (TypeId::of::<String>(), ValueKind::Text),
+            (TypeId::of::<i64>(), ValueKind::I64),
+            (TypeId::of::<i32>(), ValueKind::I32),
+            (TypeId::of::<u64>(), ValueKind::U64),
+            (TypeId::of::<u32>(), ValueKind::U32),
+            (TypeId::of::<usize>(), ValueKind::Usize),
+            (TypeId::of::<f64>(), ValueKind::F64),
+            (TypeId::of::<f32>(), ValueKind::F32),
+            (TypeId::of::<bool>(), ValueKind::Bool),
+            (TypeId::of::<Vec<u8>>(), ValueKind::Bytes),
+        ];
+        kinds
+            .iter()
+            .find(|(id, _)| *id == type_id)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Pick a hostile value of this kind; `text` is used for [`ValueKind::Text`]
+    pub(crate) fn hostile(self, rng: &mut SeededRng, text: &str) -> FuzzValue {
+        fn pick<T: Copy>(rng: &mut SeededRng, values: &[T]) -> T {
+            values[rng.next_index(values.len())]
+        }
+
+        match self {
+            ValueKind::Text => FuzzValue::Text(text.to_string()),
+            ValueKind::I64 => FuzzValue::I64(pick(
+                rng,
+                &[
+                    i64::MIN,
+                    i64::MAX,
+                    0,
+                    -1,
+                    i32::MIN as i64 - 1,
+                    i32::MAX as i64 + 1,
+                ],
+            )),
+            ValueKind::I32 => FuzzValue::I32(pick(rng, &[i32::MIN, i32::MAX, 0, -1])),
+            ValueKind::U64 => FuzzValue::U64(pick(
+                rng,
+                &[0, u64::MAX, u32::MAX as u64 + 1, i64::MAX as u64 + 1],
+            )),
+            ValueKind::U32 => FuzzValue::U32(pick(rng, &[0, u32::MAX, i32::MAX as u32 + 1])),
+            ValueKind::Usize => FuzzValue::Usize(pick(rng, &[0, usize::MAX, usize::MAX / 2 + 1])),
+            ValueKind::F64 => FuzzValue::F64(pick(
+                rng,
+                &[
+                    f64::NAN,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    -0.0,
+                    f64::MIN_POSITIVE,
+                    f64::MAX,
+                    f64::MIN,
+                ],
+            )),
+            ValueKind::F32 => FuzzValue::F32(pick(
+                rng,
+                &[
+                    f32::NAN,
+                    f32::INFINITY,
+                    f32::NEG_INFINITY,
+                    -0.0,
+                    f32::MIN_POSITIVE,
+                    f32::MAX,
+                    f32::MIN,
+                ],
+            )),
+            ValueKind::Bool => FuzzValue::Bool(rng.next_u64() & 1 == 1),
+            ValueKind::Bytes => {
+                let values: [&[u8]; 5] = [
+                    b"",
+                    b"\x00",
+                    b"\xff\xfe\xfd",
+                    b"\xef\xbb\xbf\xc0\xaf",
+                    text.as_bytes(),
+                ];
+                FuzzValue::Bytes(values[rng.next_index(values.len())].to_vec())
+            }
+        }
+    }
+}
+
+/// A hostile value injected into a context key
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzValue {
+    Text(String),
+    I64(i64),
+    I32(i32),
+    U64(u64),
+    U32(u32),
+    Usize(usize),
+    F64(f64),
+    F32(f32),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    /// A value registered with `FuzzingMiddleware::with_hostile_values`,
+    /// rendered with `Debug` (it cannot be rebuilt from the record)
+    Custom { type_name: String, debug: String },
+}
+
+impl FuzzValue {
+    /// The payload, if this is a string value
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            FuzzValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Read a stored value of one of the [`ValueKind`] types
+    pub(crate) fn from_stored(value: &(dyn Any + Send + Sync)) -> Option<Self> {
+        Some(match ValueKind::of(value)? {
+            ValueKind::Text => FuzzValue::Text(value.downcast_ref::<String>()?.clone()),
+            ValueKind::I64 => FuzzValue::I64(*value.downcast_ref()?),
+            ValueKind::I32 => FuzzValue::I32(*value.downcast_ref()?),
+            ValueKind::U64 => FuzzValue::U64(*value.downcast_ref()?),
+            ValueKind::U32 => FuzzValue::U32(*value.downcast_ref()?),
+            ValueKind::Usize => FuzzValue::Usize(*value.downcast_ref()?),
+            ValueKind::F64 => FuzzValue::F64(*value.downcast_ref()?),
+            ValueKind::F32 => FuzzValue::F32(*value.downcast_ref()?),
+            ValueKind::Bool => FuzzValue::Bool(*value.downcast_ref()?),
+            ValueKind::Bytes => FuzzValue::Bytes(value.downcast_ref::<Vec<u8>>()?.clone()),
+        })
+    }
+
+    /// Kind of the value (`None` for custom values)
+    pub fn kind(&self) -> Option<ValueKind> {
+        Some(match self {
+            FuzzValue::Text(_) => ValueKind::Text,
+            FuzzValue::I64(_) => ValueKind::I64,
+            FuzzValue::I32(_) => ValueKind::I32,
+            FuzzValue::U64(_) => ValueKind::U64,
+            FuzzValue::U32(_) => ValueKind::U32,
+            FuzzValue::Usize(_) => ValueKind::Usize,
+            FuzzValue::F64(_) => ValueKind::F64,
+            FuzzValue::F32(_) => ValueKind::F32,
+            FuzzValue::Bool(_) => ValueKind::Bool,
+            FuzzValue::Bytes(_) => ValueKind::Bytes,
+            FuzzValue::Custom { .. } => return None,
+        })
+    }
+
+    /// Rust type of the value, e.g. `"String"` or `"i64"`
+    pub fn type_name(&self) -> &str {
+        match self {
+            FuzzValue::Text(_) => "String",
+            FuzzValue::I64(_) => "i64",
+            FuzzValue::I32(_) => "i32",
+            FuzzValue::U64(_) => "u64",
+            FuzzValue::U32(_) => "u32",
+            FuzzValue::Usize(_) => "usize",
+            FuzzValue::F64(_) => "f64",
+            FuzzValue::F32(_) => "f32",
+            FuzzValue::Bool(_) => "bool",
+            FuzzValue::Bytes(_) => "Vec<u8>",
+            FuzzValue::Custom { type_name, .. } => type_name,
+        }
+    }
+
+    /// Store the value under `key`, returning `false` for custom values
+    pub fn apply(&self, context: &mut EventContext, key: &str) -> bool {
+        match self.to_shared() {
+            Some(value) => {
+                context.set_shared(key, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn to_shared(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        Some(match self {
+            FuzzValue::Text(value) => Arc::new(value.clone()),
+            FuzzValue::I64(value) => Arc::new(*value),
+            FuzzValue::I32(value) => Arc::new(*value),
+            FuzzValue::U64(value) => Arc::new(*value),
+            FuzzValue::U32(value) => Arc::new(*value),
+            FuzzValue::Usize(value) => Arc::new(*value),
+            FuzzValue::F64(value) => Arc::new(*value),
+            FuzzValue::F32(value) => Arc::new(*value),
+            FuzzValue::Bool(value) => Arc::new(*value),
+            FuzzValue::Bytes(value) => Arc::new(value.clone()),
+            FuzzValue::Custom { .. } => return None,
+        })
+    }
+
+    /// Rust expression producing the value, for generated regression tests
+    pub(crate) fn to_rust_expr(&self) -> Option<String> {
+        fn float(value: f64, ty: &str) -> String {
+            if value.is_nan() {
+                format!("{}::NAN", ty)
+            } else if value == f64::INFINITY {
+                format!("{}::INFINITY", ty)
+            } else if value == f64::NEG_INFINITY {
+                format!("{}::NEG_INFINITY", ty)
+            } else {
+                format!("{:?}{}", value, ty)
+            }
+        }
+
+        Some(match self {
+            FuzzValue::Text(value) => format!("String::from({:?})", value),
+            FuzzValue::I64(value) => format!("{}i64", value),
+            FuzzValue::I32(value) => format!("{}i32", value),
+            FuzzValue::U64(value) => format!("{}u64", value),
+            FuzzValue::U32(value) => format!("{}u32", value),
+            FuzzValue::Usize(value) => format!("{}usize", value),
+            FuzzValue::F64(value) => float(*value, "f64"),
+            FuzzValue::F32(value) => float(*value as f64, "f32"),
+            FuzzValue::Bool(value) => value.to_string(),
+            FuzzValue::Bytes(value) => format!("b\"{}\".to_vec()", value.escape_ascii()),
+            FuzzValue::Custom { .. } => return None,
+        })
+    }
+}
+
+impl fmt::Display for FuzzValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzValue::Text(value) => write!(f, "{}", value),
+            FuzzValue::I64(value) => write!(f, "{}", value),
+            FuzzValue::I32(value) => write!(f, "{}", value),
+            FuzzValue::U64(value) => write!(f, "{}", value),
+            FuzzValue::U32(value) => write!(f, "{}", value),
+            FuzzValue::Usize(value) => write!(f, "{}", value),
+            FuzzValue::F64(value) => write!(f, "{:?}", value),
+            FuzzValue::F32(value) => write!(f, "{:?}", value),
+            FuzzValue::Bool(value) => write!(f, "{}", value),
+            FuzzValue::Bytes(value) => write!(f, "b\"{}\"", value.escape_ascii()),
+            FuzzValue::Custom { debug, .. } => write!(f, "{}", debug),
+        }
+    }
+}
+
+/// User-supplied hostile values for one custom type
+#[derive(Clone)]
+pub(crate) struct HostileValues {
+    type_id: TypeId,
+    type_name: &'static str,
+    values: Vec<(Arc<dyn Any + Send + Sync>, String)>,
+}
+
+impl HostileValues {
+    pub(crate) fn new<T: Any + Send + Sync + fmt::Debug>(values: Vec<T>) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            values: values
+                .into_iter()
+                .map(|value| {
+                    let debug = format!("{:?}", value);
+                    (Arc::new(value) as Arc<dyn Any + Send + Sync>, debug)
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn value_type(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Pick one of the values (`None` if the list is empty)
+    pub(crate) fn pick(
+        &self,
+        rng: &mut SeededRng,
+    ) -> Option<(Arc<dyn Any + Send + Sync>, FuzzValue)> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let (value, debug) = &self.values[rng.next_index(self.values.len())];
+        Some((
+            value.clone(),
+            FuzzValue::Custom {
+                type_name: self.type_name.to_string(),
+                debug: debug.clone(),
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_file_skips_comments_and_empty_lines() {
+        let path = std::env::temp_dir().join(format!("sqli-{}.txt", std::process::id()));
+        fs::write(&path, "# known payloads\r\n' OR 1=1--\r\n\n  padded \n").unwrap();
+
+        let corpus = FuzzCorpus::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let corpus = corpus.unwrap();
+        assert_eq!(corpus.name(), format!("sqli-{}", std::process::id()));
+        assert_eq!(corpus.payloads(), ["' OR 1=1--", "  padded "]);
+    }
+
+    #[test]
+    fn hostile_values_keep_their_kind() {
+        let mut rng = SeededRng::new(1);
+        let kinds = [
+            ValueKind::Text,
+            ValueKind::I64,
+            ValueKind::I32,
+            ValueKind::U64,
+            ValueKind::U32,
+            ValueKind::Usize,
+            ValueKind::F64,
+            ValueKind::F32,
+            ValueKind::Bool,
+            ValueKind::Bytes,
+        ];
+
+        for kind in kinds {
+            let value = kind.hostile(&mut rng, "payload");
+            assert_eq!(value.kind(), Some(kind));
+
+            let stored = value.to_shared().unwrap();
+            assert_eq!(ValueKind::of(stored.as_ref()), Some(kind));
+            let read = FuzzValue::from_stored(stored.as_ref()).unwrap();
+            assert_eq!(read.to_string(), value.to_string());
+        }
+    }
+}
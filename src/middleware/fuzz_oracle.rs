@@ -0,0 +1,286 @@
+use crate::core::event_context::EventContext;
+use crate::core::event_result::EventResult;
+use crate::middleware::fuzzing::{FuzzInput, FuzzType};
+use crate::middleware::pattern::glob_match;
+use std::time::Duration;
+
+/// Everything an oracle can inspect about one fuzzed execution
+pub struct FuzzExecution<'a> {
+    pub event_name: &'a str,
+    pub fuzz_type: FuzzType,
+    /// The payloads that were injected
+    pub inputs: &'a [FuzzInput],
+    /// Result of the event (a caught panic is reported as a `Failure`)
+    pub result: &'a EventResult<()>,
+    /// The context after the event ran, before original values are restored
+    pub context: &'a EventContext,
+    pub elapsed: Duration,
+    /// Panic message, if the event panicked
+    pub panic_message: Option<&'a str>,
+}
+
+/// Decides whether a fuzzed execution is a real finding
+///
+/// Accepting hostile input is not a vulnerability by itself: many events
+/// legitimately store arbitrary text. Oracles look for actual evidence of
+/// a problem and return a description of it, or `None`.
+///
+/// # Example
+///
+/// ```ignore
+/// struct LeaksStackTrace;
+///
+/// impl FuzzOracle for LeaksStackTrace {
+///     fn name(&self) -> &str {
+///         "leaks_stack_trace"
+///     }
+///
+///     fn check(&self, execution: &FuzzExecution) -> Option<String> {
+///         match execution.result {
+///             EventResult::Failure(err) if err.contains("at src/") => {
+///                 Some("error message exposes source locations".to_string())
+///             }
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait FuzzOracle: Send + Sync {
+    /// Short identifier recorded in findings
+    fn name(&self) -> &str;
+
+    /// Describe the problem with this execution, or `None` if there is none
+    fn check(&self, execution: &FuzzExecution) -> Option<String>;
+}
+
+/// Reports events that panic on fuzzed input (installed by default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PanicOracle;
+
+impl FuzzOracle for PanicOracle {
+    fn name(&self) -> &str {
+        "panic"
+    }
+
+    fn check(&self, execution: &FuzzExecution) -> Option<String> {
+        execution
+            .panic_message
+            .map(|message| format!("panicked: {}", message))
+    }
+}
+
+/// Reports fuzzed executions slower than a threshold, e.g. catastrophic
+/// regex backtracking or quadratic parsing of oversized input
+#[derive(Debug, Clone, Copy)]
+pub struct SlowExecutionOracle {
+    threshold: Duration,
+}
+
+impl SlowExecutionOracle {
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+}
+
+impl FuzzOracle for SlowExecutionOracle {
+    fn name(&self) -> &str {
+        "slow_execution"
+    }
+
+    fn check(&self, execution: &FuzzExecution) -> Option<String> {
+        (execution.elapsed > self.threshold).then(|| {
+            format!("took {:?} (threshold {:?})", execution.elapsed, self.threshold)
+        })
+    }
+}
+
+/// Reports payloads copied verbatim into output keys, e.g. an XSS payload
+/// rendered into HTML without escaping
+///
+/// Output keys are glob patterns (`"html_*"`); the fuzzed keys themselves
+/// are never checked.
+#[derive(Debug, Clone)]
+pub struct ReflectedPayloadOracle {
+    output_keys: Vec<String>,
+}
+
+impl ReflectedPayloadOracle {
+    pub fn new(output_keys: &[&str]) -> Self {
+        Self {
+            output_keys: output_keys.iter().map(|key| key.to_string()).collect(),
+        }
+    }
+}
+
+impl FuzzOracle for ReflectedPayloadOracle {
+    fn name(&self) -> &str {
+        "reflected_payload"
+    }
+
+    fn check(&self, execution: &FuzzExecution) -> Option<String> {
+        let mut keys: Vec<&str> = execution
+            .context
+            .keys()
+            .filter(|key| self.output_keys.iter().any(|pattern| glob_match(pattern, key)))
+            .filter(|key| !execution.inputs.iter().any(|input| input.key == *key))
+            .collect();
+        keys.sort_unstable();
+
+        keys.into_iter().find_map(|key| {
+            let output = execution.context.get::<String>(key)?;
+            execution
+                .inputs
+                .iter()
+                .find(|input| {
+                    input
+                        .value
+                        .as_text()
+                        .is_some_and(|payload| !payload.is_empty() && output.contains(payload))
+                })
+                .map(|input| {
+                    format!("payload from '{}' reflected unescaped in '{}'", input.key, key)
+                })
+        })
+    }
+}
+
+/// Reports events that succeed although they are expected to reject
+/// hostile input
+///
+/// This is the strictest oracle: use it for events that validate their
+/// input, such as login or file access, not for events that store free text.
+#[derive(Debug, Clone)]
+pub struct ExpectRejectionOracle {
+    event_patterns: Vec<String>,
+}
+
+impl ExpectRejectionOracle {
+    /// Expect rejection from events matching any of the glob patterns
+    pub fn for_events(event_patterns: &[&str]) -> Self {
+        Self {
+            event_patterns: event_patterns.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Expect every event to reject fuzzed input
+    pub fn all() -> Self {
+        Self::for_events(&["*"])
+    }
+}
+
+impl FuzzOracle for ExpectRejectionOracle {
+    fn name(&self) -> &str {
+        "expect_rejection"
+    }
+
+    fn check(&self, execution: &FuzzExecution) -> Option<String> {
+        let expected = self
+            .event_patterns
+            .iter()
+            .any(|pattern| glob_match(pattern, execution.event_name));
+        (expected && execution.result.is_success())
+            .then(|| format!("accepted {:?} payload", execution.fuzz_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::chainable_event::ChainableEvent;
+    use crate::events::event_middleware::EventMiddleware;
+    use crate::middleware::clock::{Clock, ManualClock};
+    use crate::middleware::fuzzing::FuzzingMiddleware;
+
+    struct Render;
+
+    impl ChainableEvent for Render {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Render"
+        }
+    }
+
+    fn fuzzer<O: FuzzOracle + 'static>(oracle: O) -> FuzzingMiddleware {
+        FuzzingMiddleware::new(1.0)
+            .with_target_keys(vec!["input".to_string()])
+            .with_fuzz_types(vec![FuzzType::XssPayload])
+            .with_oracle(oracle)
+            .with_seed(1)
+            .with_logging(false)
+    }
+
+    fn run(
+        fuzzer: &FuzzingMiddleware,
+        event: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> Vec<String> {
+        let mut context = EventContext::new();
+        context.set("input", "hello".to_string());
+        fuzzer.execute(&Render, &mut context, event);
+        fuzzer.findings().into_iter().map(|finding| finding.oracle).collect()
+    }
+
+    #[test]
+    fn slow_execution_is_reported_above_the_threshold() {
+        let clock = ManualClock::new();
+        let oracle = SlowExecutionOracle::new(Duration::from_millis(100));
+        let fuzzer = fuzzer(oracle).with_clock(clock.clone());
+
+        assert!(run(&fuzzer, &mut |_| {
+            clock.sleep(Duration::from_millis(100));
+            EventResult::Success(())
+        })
+        .is_empty());
+
+        let findings = run(&fuzzer, &mut |_| {
+            clock.sleep(Duration::from_millis(101));
+            EventResult::Success(())
+        });
+        assert_eq!(findings, ["slow_execution"]);
+    }
+
+    #[test]
+    fn reflected_payload_is_reported_only_in_output_keys() {
+        let fuzzer = fuzzer(ReflectedPayloadOracle::new(&["html_*"]));
+
+        let escaped = run(&fuzzer, &mut |context| {
+            let input: String = context.get("input").unwrap();
+            context.set("html_body", input.replace('<', "&lt;"));
+            context.set("log_line", input);
+            EventResult::Success(())
+        });
+        assert!(escaped.is_empty());
+
+        let reflected = run(&fuzzer, &mut |context| {
+            let input: String = context.get("input").unwrap();
+            context.set("html_body", format!("<p>{}</p>", input));
+            EventResult::Success(())
+        });
+        assert_eq!(reflected, ["reflected_payload"]);
+        assert!(fuzzer.findings()[0].detail.contains("'input' reflected unescaped in 'html_body'"));
+    }
+
+    #[test]
+    fn expect_rejection_reports_accepted_input_for_matching_events() {
+        let other = fuzzer(ExpectRejectionOracle::for_events(&["Login*"]));
+        assert!(run(&other, &mut |_| EventResult::Success(())).is_empty());
+
+        let fuzzer = fuzzer(ExpectRejectionOracle::all());
+        assert!(run(&fuzzer, &mut |_| EventResult::Failure("invalid".to_string())).is_empty());
+        assert_eq!(run(&fuzzer, &mut |_| EventResult::Success(())), ["expect_rejection"]);
+
+        let stats = fuzzer.get_stats().unwrap();
+        assert_eq!((stats.accepted_inputs, stats.detected_vulnerabilities), (1, 1));
+    }
+
+    #[test]
+    fn accepted_input_is_not_a_finding_by_default() {
+        let fuzzer = fuzzer(PanicOracle);
+
+        assert!(run(&fuzzer, &mut |_| EventResult::Success(())).is_empty());
+        let stats = fuzzer.get_stats().unwrap();
+        assert_eq!((stats.accepted_inputs, stats.detected_vulnerabilities), (1, 0));
+    }
+}
@@ -2,6 +2,19 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::fuzz_corpus::{FuzzCorpus, FuzzValue, HostileValues, ValueKind};
+use crate::middleware::fuzz_oracle::{FuzzExecution, FuzzOracle, PanicOracle};
+use crate::middleware::log_sink::escape_json;
+use crate::middleware::logging::LogLevel;
+use crate::middleware::pattern::glob_match;
+use crate::middleware::redaction::Redactor;
+use crate::middleware::rng::{SeededRng, random_seed};
+use std::any::Any;
+use std::fmt::Debug;
+use std::io::Write;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::sync::{Arc, Mutex};
 
 /// Types of malicious/edge-case inputs to inject
@@ -33,6 +46,8 @@ pub enum FuzzType {
     EmptyInput,
     /// Deeply nested structures (DoS)
     DeeplyNested,
+    /// Payloads from the user-supplied corpora (see [`FuzzConfig::corpora`])
+    Corpus,
 }
 
 /// Predefined malicious payloads for each fuzz type
@@ -156,12 +171,12 @@ impl FuzzPayloads {
     fn deeply_nested() -> String {
         let mut result = String::from("[");
         for _ in 0..10000 {
-            result.push_str("[");
+            result.push('[');
         }
         for _ in 0..10000 {
-            result.push_str("]");
+            result.push(']');
         }
-        result.push_str("]");
+        result.push(']');
         result
     }
 }
@@ -173,8 +188,38 @@ pub struct FuzzConfig {
     pub probability: f64,
     /// Types of fuzzing to potentially inject
     pub fuzz_types: Vec<FuzzType>,
-    /// Context keys to inject fuzzing into
+    /// Context keys to inject fuzzing into (ignored when `discover_keys` is set)
     pub target_keys: Vec<String>,
+    /// Fuzz every `String` key present in the context when the event starts
+    /// (default `false`), instead of the fixed `target_keys`
+    pub discover_keys: bool,
+    /// Glob patterns a discovered key must match; empty matches every key
+    pub include_keys: Vec<String>,
+    /// Glob patterns excluding discovered keys, e.g. `"session_*"`
+    pub exclude_keys: Vec<String>,
+    /// Seed for the random number generator
+    ///
+    /// `None` picks a random seed, which is recorded in [`FuzzStats::seed`]
+    /// and in every [`FuzzFinding`].
+    pub seed: Option<u64>,
+    /// Put the original values back after the event runs (default `true`)
+    ///
+    /// Keys that did not exist before fuzzing are removed again. With
+    /// `false` the payloads stay in the context for later events.
+    pub restore_context: bool,
+    /// User-supplied payload lists, used by [`FuzzType::Corpus`]
+    pub corpora: Vec<FuzzCorpus>,
+    /// Value type to generate for specific keys, overriding type detection
+    pub key_kinds: Vec<(String, ValueKind)>,
+    /// Replace existing values with hostile values of the same type
+    /// (default `true`)
+    ///
+    /// An `i64` gets boundary integers, a `bool` a flipped or random flag,
+    /// and types registered with [`FuzzingMiddleware::with_hostile_values`]
+    /// one of their hostile values, so events get past their downcasts.
+    /// Keys holding any other type are left alone. With `false` every key
+    /// receives a `String` payload.
+    pub type_preserving: bool,
 }
 
 impl Default for FuzzConfig {
@@ -194,15 +239,198 @@ impl Default for FuzzConfig {
                 "filename".to_string(),
                 "query".to_string(),
             ],
+            discover_keys: false,
+            include_keys: Vec::new(),
+            exclude_keys: Vec::new(),
+            seed: None,
+            restore_context: true,
+            corpora: Vec::new(),
+            key_kinds: Vec::new(),
+            type_preserving: true,
         }
     }
 }
 
+/// Value of a target key before injection, and the payload that replaced it
+struct InjectedKey {
+    key: String,
+    original: Option<Arc<dyn Any + Send + Sync>>,
+    injected: Arc<dyn Any + Send + Sync>,
+}
+
+/// A hostile value written into one context key
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzInput {
+    pub key: String,
+    pub value: FuzzValue,
+}
+
+/// What the event returned when executed with fuzzed input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzOutcome {
+    /// The event succeeded
+    Accepted,
+    /// The event returned `EventResult::Failure`
+    Rejected(String),
+    /// A middleware returned `EventResult::MiddlewareFailure`
+    MiddlewareFailure(String),
+    /// The event panicked; holds the panic message
+    Panicked(String),
+}
+
+impl FuzzOutcome {
+    fn from_result(result: &EventResult<()>, panic_message: Option<&str>) -> Self {
+        if let Some(message) = panic_message {
+            return FuzzOutcome::Panicked(message.to_string());
+        }
+        match result {
+            EventResult::Success(_) => FuzzOutcome::Accepted,
+            EventResult::Failure(err) => FuzzOutcome::Rejected(err.clone()),
+            EventResult::MiddlewareFailure(err) => FuzzOutcome::MiddlewareFailure(err.clone()),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FuzzOutcome::Accepted => "accepted",
+            FuzzOutcome::Rejected(_) => "rejected",
+            FuzzOutcome::MiddlewareFailure(_) => "middleware_failure",
+            FuzzOutcome::Panicked(_) => "panicked",
+        }
+    }
+
+    fn error(&self) -> Option<&str> {
+        match self {
+            FuzzOutcome::Accepted => None,
+            FuzzOutcome::Rejected(err)
+            | FuzzOutcome::MiddlewareFailure(err)
+            | FuzzOutcome::Panicked(err) => Some(err),
+        }
+    }
+}
+
+/// A reproducible record of a problem reported by a [`FuzzOracle`]
+///
+/// Contains everything needed to replay the case: the exact payloads and
+/// keys, the event, and the seed and attempt number of the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzFinding {
+    /// Seed of the fuzzing run
+    pub seed: u64,
+    /// 1-based number of the fuzzing attempt within the run
+    pub attempt: u64,
+    pub event_name: String,
+    pub fuzz_type: FuzzType,
+    pub inputs: Vec<FuzzInput>,
+    pub outcome: FuzzOutcome,
+    /// Name of the oracle that reported the finding
+    pub oracle: String,
+    /// The oracle's description of the problem
+    pub detail: String,
+}
+
+impl FuzzFinding {
+    /// Write the finding's values into `context`, to replay it
+    ///
+    /// Custom values cannot be rebuilt from the record and are skipped.
+    pub fn apply(&self, context: &mut EventContext) {
+        for input in &self.inputs {
+            input.value.apply(context, &input.key);
+        }
+    }
+
+    /// Render as a single-line JSON object (without trailing newline)
+    pub fn to_json(&self) -> String {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| {
+                format!(
+                    "{{\"key\":\"{}\",\"type\":\"{}\",\"payload\":\"{}\"}}",
+                    escape_json(&input.key),
+                    escape_json(input.value.type_name()),
+                    escape_json(&input.value.to_string())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let error = match self.outcome.error() {
+            Some(err) => format!("\"{}\"", escape_json(err)),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"seed\":{},\"attempt\":{},\"event\":\"{}\",\"fuzz_type\":\"{:?}\",\"inputs\":[{}],\"outcome\":\"{}\",\"error\":{},\"oracle\":\"{}\",\"detail\":\"{}\"}}",
+            self.seed,
+            self.attempt,
+            escape_json(&self.event_name),
+            self.fuzz_type,
+            inputs,
+            self.outcome.as_str(),
+            error,
+            escape_json(&self.oracle),
+            escape_json(&self.detail)
+        )
+    }
+
+    /// Render a Rust regression test that replays this finding
+    ///
+    /// The test expects the event to reject the input; `event_expr` is the
+    /// expression constructing the event, e.g. `"QueryUsers::new()"`. Keys
+    /// holding custom values cannot be rebuilt from the record and are left out.
+    pub fn to_regression_test(&self, event_expr: &str) -> String {
+        let mut test = format!(
+            "#[test]\nfn fuzz_{}_{}_{}() {{\n    // {}: {}\n    // {:?} payload, seed {}, attempt {}\n    let mut context = EventContext::new();\n",
+            sanitize_ident(&self.event_name),
+            self.seed,
+            self.attempt,
+            self.oracle,
+            self.detail.escape_default(),
+            self.fuzz_type,
+            self.seed,
+            self.attempt
+        );
+        for input in &self.inputs {
+            if let Some(expr) = input.value.to_rust_expr() {
+                test.push_str(&format!("    context.set({:?}, {});\n", input.key, expr));
+            }
+        }
+        test.push_str(&format!(
+            "    let result = {}.execute(&mut context);\n    assert!(!result.is_success(), \"{} must reject this input\");\n}}\n",
+            event_expr,
+            self.event_name.escape_default()
+        ));
+        test
+    }
+}
+
+/// Extract the message from a caught panic payload
+fn describe_panic(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+fn sanitize_ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
 /// Statistics about fuzzing attempts
 #[derive(Debug, Clone, Default)]
 pub struct FuzzStats {
+    /// Seed of the random number generator; pass it to
+    /// [`FuzzingMiddleware::with_seed`] to replay the same payload sequence
+    pub seed: u64,
     pub total_events: u64,
     pub fuzzing_attempts: u64,
+    /// Fuzzed executions the event accepted; not a problem by itself
+    pub accepted_inputs: u64,
+    /// Findings reported by oracles (see [`FuzzingMiddleware::findings`])
     pub detected_vulnerabilities: u64,
     pub sql_injection_tests: u64,
     pub xss_tests: u64,
@@ -236,9 +464,18 @@ pub struct FuzzStats {
 /// Before event execution, this middleware:
 /// 1. Checks if fuzzing should occur (based on probability)
 /// 2. Selects a random fuzz type
-/// 3. Injects malicious payloads into configured context keys
+/// 3. Injects malicious payloads into configured (or discovered) context keys
 /// 4. Executes the event with tainted data
-/// 5. Monitors for unexpected failures or behaviors
+/// 5. Asks its oracles whether the execution revealed a problem
+/// 6. Restores the original values of the target keys (unless disabled with
+///    [`with_restore`](Self::with_restore)); keys the event overwrote are kept
+///
+/// # Panics in Fuzzed Events
+///
+/// A panic in the event (or in middleware added before this one) is caught
+/// and returned as `EventResult::Failure`, so the chain carries on according
+/// to its fault tolerance mode. The default [`PanicOracle`] records it as a
+/// finding with outcome [`FuzzOutcome::Panicked`].
 ///
 /// # What to Look For
 ///
@@ -249,6 +486,16 @@ pub struct FuzzStats {
 /// - **Performance degradation**: DoS vulnerability
 /// - **Different behavior**: Logic bugs with edge cases
 ///
+/// # Reproducing Findings
+///
+/// Payloads are chosen by a seeded generator shared by clones. Every
+/// suspicious execution is recorded as a [`FuzzFinding`] (see
+/// [`findings`](Self::findings)), which can be exported as JSON lines with
+/// [`export_findings`](Self::export_findings), replayed with
+/// [`FuzzFinding::apply`], or turned into a regression test with
+/// [`FuzzFinding::to_regression_test`]. Rerunning with the recorded seed
+/// (single-threaded) repeats the exact payload sequence.
+///
 /// # Example
 ///
 /// ```ignore
@@ -277,13 +524,20 @@ pub struct FuzzStats {
 /// }
 ///
 /// fuzzer.print_stats();
+/// fuzzer.export_findings(std::fs::File::create("findings.jsonl")?)?;
 /// ```
 #[derive(Clone)]
 pub struct FuzzingMiddleware {
     config: FuzzConfig,
     stats: Arc<Mutex<FuzzStats>>,
+    findings: Arc<Mutex<Vec<FuzzFinding>>>,
+    oracles: Vec<Arc<dyn FuzzOracle>>,
+    hostile_values: Vec<HostileValues>,
+    rng: Arc<Mutex<SeededRng>>,
+    clock: SharedClock,
     enabled: Arc<Mutex<bool>>,
     log_fuzzing: bool,
+    redactor: Redactor,
 }
 
 impl FuzzingMiddleware {
@@ -297,12 +551,53 @@ impl FuzzingMiddleware {
 
     /// Create fuzzing middleware with full configuration
     pub fn with_config(config: FuzzConfig) -> Self {
+        let seed = config.seed.unwrap_or_else(random_seed);
         Self {
             config,
-            stats: Arc::new(Mutex::new(FuzzStats::default())),
+            stats: Arc::new(Mutex::new(FuzzStats {
+                seed,
+                ..Default::default()
+            })),
+            findings: Arc::new(Mutex::new(Vec::new())),
+            oracles: vec![Arc::new(PanicOracle)],
+            hostile_values: Vec::new(),
+            rng: Arc::new(Mutex::new(SeededRng::new(seed))),
+            clock: SystemClock::shared(),
             enabled: Arc::new(Mutex::new(true)),
             log_fuzzing: true,
+            redactor: Redactor::new(),
+        }
+    }
+
+    /// Use a fixed seed, e.g. the one recorded in a [`FuzzFinding`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self.rng = Arc::new(Mutex::new(SeededRng::new(seed)));
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.seed = seed;
         }
+        self
+    }
+
+    /// Get the seed of the random number generator
+    pub fn seed(&self) -> u64 {
+        self.stats.lock().map(|s| s.seed).unwrap_or_default()
+    }
+
+    /// Add an oracle deciding what counts as a finding
+    ///
+    /// Only [`PanicOracle`] is installed by default, so events that merely
+    /// accept hostile input are counted in [`FuzzStats::accepted_inputs`]
+    /// but not reported as findings.
+    pub fn with_oracle<O: FuzzOracle + 'static>(mut self, oracle: O) -> Self {
+        self.oracles.push(Arc::new(oracle));
+        self
+    }
+
+    /// Measure execution time (for `SlowExecutionOracle`) with `clock`
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
     }
 
     /// Set specific fuzz types to use
@@ -317,6 +612,79 @@ impl FuzzingMiddleware {
         self
     }
 
+    /// Fuzz every `String` key present when the event starts, instead of a
+    /// fixed list of target keys
+    ///
+    /// Keys must match one of the `include` glob patterns (all keys if
+    /// empty) and none of the `exclude` patterns. Discovery happens anew for
+    /// each event, so keys written by earlier events are fuzzed too.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Everything except secrets and framework keys
+    /// let fuzzer = FuzzingMiddleware::new(0.3)
+    ///     .with_key_discovery(&[], &["*token*", "*secret*", "fallback:*"]);
+    ///
+    /// // Only request fields
+    /// let fuzzer = FuzzingMiddleware::new(0.3).with_key_discovery(&["request.*"], &[]);
+    /// ```
+    pub fn with_key_discovery(mut self, include: &[&str], exclude: &[&str]) -> Self {
+        self.config.discover_keys = true;
+        self.config.include_keys = include.iter().map(|p| p.to_string()).collect();
+        self.config.exclude_keys = exclude.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Add a payload corpus and enable [`FuzzType::Corpus`]
+    pub fn with_corpus(mut self, corpus: FuzzCorpus) -> Self {
+        self.config.corpora.push(corpus);
+        if !self.config.fuzz_types.contains(&FuzzType::Corpus) {
+            self.config.fuzz_types.push(FuzzType::Corpus);
+        }
+        self
+    }
+
+    /// Generate values of `kind` for `key`, e.g. boundary integers for a
+    /// key that may be absent before the event runs
+    pub fn with_key_kind(mut self, key: &str, kind: ValueKind) -> Self {
+        self.config.key_kinds.retain(|(k, _)| k != key);
+        self.config.key_kinds.push((key.to_string(), kind));
+        self
+    }
+
+    /// Preserve value types when injecting (default), or always inject strings
+    pub fn with_type_preserving(mut self, enabled: bool) -> Self {
+        self.config.type_preserving = enabled;
+        self
+    }
+
+    /// Hostile values to inject into keys holding a `T`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let fuzzer = FuzzingMiddleware::new(0.5)
+    ///     .with_target_keys(vec!["order".to_string()])
+    ///     .with_hostile_values(vec![
+    ///         Order { quantity: 0, unit_price: -1 },
+    ///         Order { quantity: u32::MAX, unit_price: i64::MAX },
+    ///     ]);
+    /// ```
+    pub fn with_hostile_values<T: Any + Send + Sync + Debug>(mut self, values: Vec<T>) -> Self {
+        let values = HostileValues::new(values);
+        self.hostile_values.retain(|v| v.value_type() != values.value_type());
+        self.hostile_values.push(values);
+        self
+    }
+
+    /// Restore original context values after each fuzzed event (default), or
+    /// keep the payloads so they flow into later events
+    pub fn with_restore(mut self, restore: bool) -> Self {
+        self.config.restore_context = restore;
+        self
+    }
+
     /// Enable or disable fuzzing at runtime
     pub fn set_enabled(&self, enabled: bool) {
         if let Ok(mut e) = self.enabled.lock() {
@@ -335,22 +703,55 @@ impl FuzzingMiddleware {
         self
     }
 
+    /// Mask injected payloads for sensitive keys in log output
+    ///
+    /// Share the same [`Redactor`] with `LoggingMiddleware` so that neither
+    /// middleware prints values stored under secret keys.
+    pub fn with_redaction(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
     /// Get current fuzzing statistics
     pub fn get_stats(&self) -> Option<FuzzStats> {
         self.stats.lock().ok().map(|s| s.clone())
     }
 
-    /// Reset statistics
+    /// Reset statistics (the seed is kept)
     pub fn reset_stats(&self) {
         if let Ok(mut stats) = self.stats.lock() {
-            *stats = FuzzStats::default();
+            *stats = FuzzStats {
+                seed: stats.seed,
+                ..Default::default()
+            };
+        }
+    }
+
+    /// Get the findings recorded so far
+    pub fn findings(&self) -> Vec<FuzzFinding> {
+        self.findings.lock().map(|f| f.clone()).unwrap_or_default()
+    }
+
+    /// Discard recorded findings
+    pub fn clear_findings(&self) {
+        if let Ok(mut findings) = self.findings.lock() {
+            findings.clear();
         }
     }
 
+    /// Write recorded findings to `writer`, one JSON object per line
+    pub fn export_findings<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for finding in self.findings() {
+            writeln!(writer, "{}", finding.to_json())?;
+        }
+        writer.flush()
+    }
+
     /// Print statistics to stdout
     pub fn print_stats(&self) {
         if let Ok(stats) = self.stats.lock() {
             println!("\n=== Security Fuzzing Statistics ===");
+            println!("Seed:                            {}", stats.seed);
             println!("Total events:                    {}", stats.total_events);
             println!("Fuzzing attempts:                {} ({:.1}%)",
                      stats.fuzzing_attempts,
@@ -365,24 +766,19 @@ impl FuzzingMiddleware {
             println!("  - Path traversal tests:        {}", stats.path_traversal_tests);
             println!("  - Overflow tests:              {}", stats.overflow_tests);
             println!("  - Other tests:                 {}", stats.other_tests);
-            println!("Potential vulnerabilities:       {}", stats.detected_vulnerabilities);
+            println!("Accepted hostile input:          {}", stats.accepted_inputs);
+            println!("Findings:                        {}", stats.detected_vulnerabilities);
             println!();
         }
     }
 
-    fn should_fuzz(&self) -> bool {
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
-
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+    fn with_rng<T>(&self, f: impl FnOnce(&mut SeededRng) -> T) -> T {
+        let mut rng = self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut rng)
+    }
 
-        let random_value = (hasher.finish() % 10000) as f64 / 10000.0;
-        random_value < self.config.probability
+    fn should_fuzz(&self) -> bool {
+        self.with_rng(|rng| rng.next_f64()) < self.config.probability
     }
 
     fn select_fuzz_type(&self) -> FuzzType {
@@ -390,111 +786,185 @@ impl FuzzingMiddleware {
             return FuzzType::SqlInjection;
         }
 
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
-
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
-
-        let idx = (hasher.finish() as usize) % self.config.fuzz_types.len();
+        let idx = self.with_rng(|rng| rng.next_index(self.config.fuzz_types.len()));
         self.config.fuzz_types[idx]
     }
 
     fn get_payload(&self, fuzz_type: FuzzType) -> String {
-        use std::collections::hash_map::RandomState;
-        use std::hash::{BuildHasher, Hash, Hasher};
-
-        let mut hasher = RandomState::new().build_hasher();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
-
-        match fuzz_type {
-            FuzzType::SqlInjection => {
-                let payloads = FuzzPayloads::sql_injection();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::XssPayload => {
-                let payloads = FuzzPayloads::xss_payload();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::PathTraversal => {
-                let payloads = FuzzPayloads::path_traversal();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::OversizedInput => FuzzPayloads::oversized_input(),
-            FuzzType::NullBytes => {
-                let payloads = FuzzPayloads::null_bytes();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::UnicodeEdgeCases => {
-                let payloads = FuzzPayloads::unicode_edge_cases();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::IntegerOverflow => {
-                let payloads = FuzzPayloads::integer_overflow();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::FormatString => {
-                let payloads = FuzzPayloads::format_string();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::CommandInjection => {
-                let payloads = FuzzPayloads::command_injection();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::LdapInjection => {
-                let payloads = FuzzPayloads::ldap_injection();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::XmlInjection => {
-                let payloads = FuzzPayloads::xml_injection();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
-            }
-            FuzzType::EmptyInput => {
-                let payloads = FuzzPayloads::empty_input();
-                let idx = (hasher.finish() as usize) % payloads.len();
-                payloads[idx].to_string()
+        let payloads = match fuzz_type {
+            FuzzType::SqlInjection => FuzzPayloads::sql_injection(),
+            FuzzType::XssPayload => FuzzPayloads::xss_payload(),
+            FuzzType::PathTraversal => FuzzPayloads::path_traversal(),
+            FuzzType::OversizedInput => return FuzzPayloads::oversized_input(),
+            FuzzType::NullBytes => FuzzPayloads::null_bytes(),
+            FuzzType::UnicodeEdgeCases => FuzzPayloads::unicode_edge_cases(),
+            FuzzType::IntegerOverflow => FuzzPayloads::integer_overflow(),
+            FuzzType::FormatString => FuzzPayloads::format_string(),
+            FuzzType::CommandInjection => FuzzPayloads::command_injection(),
+            FuzzType::LdapInjection => FuzzPayloads::ldap_injection(),
+            FuzzType::XmlInjection => FuzzPayloads::xml_injection(),
+            FuzzType::EmptyInput => FuzzPayloads::empty_input(),
+            FuzzType::DeeplyNested => return FuzzPayloads::deeply_nested(),
+            FuzzType::Corpus => return self.corpus_payload(),
+        };
+
+        let idx = self.with_rng(|rng| rng.next_index(payloads.len()));
+        payloads[idx].to_string()
+    }
+
+    /// Pick a payload from all corpora, weighted by their size
+    fn corpus_payload(&self) -> String {
+        let total: usize = self.config.corpora.iter().map(FuzzCorpus::len).sum();
+        if total == 0 {
+            return String::new();
+        }
+
+        let idx = self.with_rng(|rng| rng.next_index(total));
+        self.config
+            .corpora
+            .iter()
+            .flat_map(FuzzCorpus::payloads)
+            .nth(idx)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Choose the hostile value for `key`, given what it currently holds
+    fn hostile_value(
+        &self,
+        key: &str,
+        existing: Option<&(dyn Any + Send + Sync + 'static)>,
+        text: &str,
+    ) -> Option<(Arc<dyn Any + Send + Sync>, FuzzValue)> {
+        let configured = self
+            .config
+            .key_kinds
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, kind)| *kind);
+
+        let kind = match (configured, existing) {
+            (Some(kind), _) => kind,
+            (None, Some(existing)) if self.config.type_preserving => {
+                match ValueKind::of(existing) {
+                    Some(kind) => kind,
+                    None => {
+                        let type_id = existing.type_id();
+                        let values =
+                            self.hostile_values.iter().find(|v| v.value_type() == type_id)?;
+                        return self.with_rng(|rng| values.pick(rng));
+                    }
+                }
             }
-            FuzzType::DeeplyNested => FuzzPayloads::deeply_nested(),
+            _ => ValueKind::Text,
+        };
+
+        let value = match (kind, existing.and_then(|e| e.downcast_ref::<bool>())) {
+            // Flip existing flags, so every injection changes the value
+            (ValueKind::Bool, Some(flag)) => FuzzValue::Bool(!flag),
+            _ => self.with_rng(|rng| kind.hostile(rng, text)),
+        };
+        Some((value.to_shared()?, value))
+    }
+
+    /// Keys to fuzz in this execution: the configured target keys, or the
+    /// discovered string keys (sorted, so runs are reproducible)
+    fn target_keys(&self, context: &EventContext) -> Vec<String> {
+        if !self.config.discover_keys {
+            return self.config.target_keys.clone();
         }
+
+        let matches_any =
+            |patterns: &[String], key: &str| patterns.iter().any(|p| glob_match(p, key));
+        let mut keys: Vec<String> = context
+            .keys_of_type::<String>()
+            .filter(|key| {
+                self.config.include_keys.is_empty() || matches_any(&self.config.include_keys, key)
+            })
+            .filter(|key| !matches_any(&self.config.exclude_keys, key))
+            .map(String::from)
+            .collect();
+        keys.sort();
+        keys
     }
 
-    fn inject_payload(&self, context: &mut EventContext, fuzz_type: FuzzType) {
-        if self.config.target_keys.is_empty() {
-            return;
+    /// Inject hostile values into the target keys, returning what was
+    /// injected and what each key held before
+    fn inject_payload(
+        &self,
+        context: &mut EventContext,
+        fuzz_type: FuzzType,
+    ) -> (Vec<FuzzInput>, Vec<InjectedKey>) {
+        let target_keys = self.target_keys(context);
+        if target_keys.is_empty() {
+            return (Vec::new(), Vec::new());
         }
 
-        let payload = self.get_payload(fuzz_type);
+        // One text payload per execution, shared by all string keys
+        let text = self.get_payload(fuzz_type);
 
-        // Inject into all target keys
-        for key in &self.config.target_keys {
-            // Store original value for potential recovery
-            let backup_key = format!("__fuzz_backup_{}", key);
-            if let Some(original) = context.get::<String>(key) {
-                context.set(&backup_key, original);
-            }
+        let mut inputs = Vec::new();
+        let mut injected = Vec::new();
+        for key in &target_keys {
+            let original = context.get_shared(key);
+            let Some((shared, value)) = self.hostile_value(key, original.as_deref(), &text) else {
+                continue;
+            };
+
+            context.set_shared(key, shared.clone());
+            inputs.push(FuzzInput {
+                key: key.clone(),
+                value,
+            });
+            injected.push(InjectedKey {
+                key: key.clone(),
+                original,
+                injected: shared,
+            });
+        }
+
+        (inputs, injected)
+    }
 
-            // Inject malicious payload
-            context.set(key, payload.clone());
+    /// Undo an injection, leaving keys the event overwrote untouched
+    fn restore_context(&self, context: &mut EventContext, injected: Vec<InjectedKey>) {
+        for InjectedKey { key, original, injected } in injected {
+            let unchanged = context
+                .get_shared(&key)
+                .is_some_and(|current| Arc::ptr_eq(&current, &injected));
+            if !unchanged {
+                continue;
+            }
+            match original {
+                Some(original) => context.set_shared(&key, original),
+                None => {
+                    context.remove(&key);
+                }
+            }
         }
     }
+
+    /// Describe injected values for logging, e.g. `query="' OR 1=1--", password=[REDACTED]`
+    fn describe_injection(&self, inputs: &[FuzzInput]) -> String {
+        const PREVIEW_CHARS: usize = 40;
+
+        inputs
+            .iter()
+            .map(|input| {
+                let rendered = input.value.to_string();
+                let mut preview: String = rendered.chars().take(PREVIEW_CHARS).collect();
+                if rendered.chars().count() > PREVIEW_CHARS {
+                    preview.push_str("...");
+                }
+                let preview = match input.value {
+                    FuzzValue::Text(_) => format!("{:?}", preview),
+                    _ => preview,
+                };
+                format!("{}={}", input.key, self.redactor.redact(&input.key, &preview))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 impl EventMiddleware for FuzzingMiddleware {
@@ -523,36 +993,118 @@ impl EventMiddleware for FuzzingMiddleware {
         let fuzz_type = self.select_fuzz_type();
 
         // Update stats
-        if let Ok(mut stats) = self.stats.lock() {
-            stats.fuzzing_attempts += 1;
-            match fuzz_type {
-                FuzzType::SqlInjection => stats.sql_injection_tests += 1,
-                FuzzType::XssPayload => stats.xss_tests += 1,
-                FuzzType::PathTraversal => stats.path_traversal_tests += 1,
-                FuzzType::OversizedInput | FuzzType::IntegerOverflow => stats.overflow_tests += 1,
-                _ => stats.other_tests += 1,
+        let attempt = match self.stats.lock() {
+            Ok(mut stats) => {
+                stats.fuzzing_attempts += 1;
+                match fuzz_type {
+                    FuzzType::SqlInjection => stats.sql_injection_tests += 1,
+                    FuzzType::XssPayload => stats.xss_tests += 1,
+                    FuzzType::PathTraversal => stats.path_traversal_tests += 1,
+                    FuzzType::OversizedInput | FuzzType::IntegerOverflow => {
+                        stats.overflow_tests += 1
+                    }
+                    _ => stats.other_tests += 1,
+                }
+                stats.fuzzing_attempts
             }
-        }
+            Err(_) => 0,
+        };
+
+        // Inject malicious payload
+        let (inputs, injected) = self.inject_payload(context, fuzz_type);
 
         if self.log_fuzzing {
-            println!("    [FUZZ] Injecting {:?} payload in {}", fuzz_type, event.name());
+            let message = if inputs.is_empty() {
+                format!("[FUZZ] Injecting {:?} payload in {}", fuzz_type, event.name())
+            } else {
+                format!(
+                    "[FUZZ] Injecting {:?} payload in {} ({})",
+                    fuzz_type,
+                    event.name(),
+                    self.describe_injection(&inputs)
+                )
+            };
+            emit(LogLevel::Info, "fuzzing", event.name(), &message);
         }
 
-        // Inject malicious payload
-        self.inject_payload(context, fuzz_type);
+        // Execute event with tainted data, turning panics into failures
+        let start = self.clock.now();
+        let outcome = catch_unwind(AssertUnwindSafe(|| next(context)));
+        let elapsed = self.clock.now().duration_since(start);
+        let (result, panic_message) = match outcome {
+            Ok(result) => (result, None),
+            Err(panic) => {
+                let message = describe_panic(panic.as_ref());
+                (
+                    EventResult::Failure(format!(
+                        "{} panicked with fuzzed input: {}",
+                        event.name(),
+                        message
+                    )),
+                    Some(message),
+                )
+            }
+        };
+
+        // Ask the oracles before restoring, so they see the event's output
+        let execution = FuzzExecution {
+            event_name: event.name(),
+            fuzz_type,
+            inputs: &inputs,
+            result: &result,
+            context,
+            elapsed,
+            panic_message: panic_message.as_deref(),
+        };
+        let verdicts: Vec<(String, String)> = self
+            .oracles
+            .iter()
+            .filter_map(|oracle| {
+                oracle
+                    .check(&execution)
+                    .map(|detail| (oracle.name().to_string(), detail))
+            })
+            .collect();
+
+        if self.config.restore_context {
+            self.restore_context(context, injected);
+        }
 
-        // Execute event with tainted data
-        let result = next(context);
+        if let Ok(mut stats) = self.stats.lock() {
+            if result.is_success() {
+                stats.accepted_inputs += 1;
+            }
+            stats.detected_vulnerabilities += verdicts.len() as u64;
+        }
 
-        // Analyze result for potential vulnerabilities
-        // If the event succeeds with malicious input, it might indicate a vulnerability
-        if result.is_success() {
+        let seed = self.seed();
+        for (oracle, detail) in verdicts {
             if self.log_fuzzing {
-                println!("   ️  [FUZZ] Event {} succeeded with {:?} payload - potential vulnerability!",
-                         event.name(), fuzz_type);
+                emit(
+                    LogLevel::Warn,
+                    "fuzzing",
+                    event.name(),
+                    &format!(
+                        "[FUZZ] Finding in {} with {:?} payload ({}): {}",
+                        event.name(),
+                        fuzz_type,
+                        oracle,
+                        detail
+                    ),
+                );
             }
-            if let Ok(mut stats) = self.stats.lock() {
-                stats.detected_vulnerabilities += 1;
+            let finding = FuzzFinding {
+                seed,
+                attempt,
+                event_name: event.name().to_string(),
+                fuzz_type,
+                inputs: inputs.clone(),
+                outcome: FuzzOutcome::from_result(&result, panic_message.as_deref()),
+                oracle,
+                detail,
+            };
+            if let Ok(mut findings) = self.findings.lock() {
+                findings.push(finding);
             }
         }
 
@@ -565,3 +1117,328 @@ impl Default for FuzzingMiddleware {
         Self::new(0.2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::fuzz_oracle::ExpectRejectionOracle;
+
+    /// Panics on input containing a quote
+    struct Parse;
+
+    impl ChainableEvent for Parse {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            let input: String = context.get("input").unwrap_or_default();
+            if input.contains('\'') {
+                panic!("unbalanced quote");
+            }
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Parse"
+        }
+    }
+
+    fn fuzzer(probability: f64) -> FuzzingMiddleware {
+        FuzzingMiddleware::new(probability)
+            .with_target_keys(vec!["input".to_string()])
+            .with_logging(false)
+    }
+
+    fn input_context(input: &str) -> EventContext {
+        let mut context = EventContext::new();
+        context.set("input", input.to_string());
+        context
+    }
+
+    /// Run `Parse` `calls` times, returning the `input` each call saw
+    fn seen_inputs(fuzzer: &FuzzingMiddleware, calls: usize) -> Vec<String> {
+        (0..calls)
+            .map(|_| {
+                let mut seen = String::new();
+                fuzzer.execute(&Parse, &mut input_context("plain"), &mut |context| {
+                    seen = context.get("input").unwrap_or_default();
+                    EventResult::Success(())
+                });
+                seen
+            })
+            .collect()
+    }
+
+    fn finding(inputs: Vec<FuzzInput>) -> FuzzFinding {
+        FuzzFinding {
+            seed: 7,
+            attempt: 3,
+            event_name: "Parse".to_string(),
+            fuzz_type: FuzzType::SqlInjection,
+            inputs,
+            outcome: FuzzOutcome::Panicked("bad \"quote\"".to_string()),
+            oracle: "panic".to_string(),
+            detail: "panicked: bad \"quote\"".to_string(),
+        }
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_payloads() {
+        let first = fuzzer(0.5).with_seed(42);
+        let second = fuzzer(0.5).with_seed(42);
+
+        let payloads = seen_inputs(&first, 40);
+        assert_eq!(payloads, seen_inputs(&second, 40));
+        assert!(payloads.iter().any(|input| input != "plain"));
+        assert_eq!(first.get_stats().unwrap().seed, 42);
+    }
+
+    #[test]
+    fn panic_in_the_event_becomes_a_failure_and_a_finding() {
+        let fuzzer = fuzzer(1.0).with_fuzz_types(vec![FuzzType::SqlInjection]).with_seed(1);
+        let mut context = input_context("plain");
+
+        let result = fuzzer.execute(&Parse, &mut context, &mut |context| Parse.execute(context));
+
+        assert!(matches!(result, EventResult::Failure(ref e) if e.contains("panicked")));
+        let findings = fuzzer.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].seed, 1);
+        assert_eq!(findings[0].attempt, 1);
+        assert_eq!(findings[0].outcome, FuzzOutcome::Panicked("unbalanced quote".to_string()));
+        assert_eq!(findings[0].inputs[0].key, "input");
+    }
+
+    #[test]
+    fn original_value_is_restored_after_the_event() {
+        let fuzzer = fuzzer(1.0).with_seed(1);
+        let mut context = input_context("plain");
+
+        let mut seen = String::new();
+        fuzzer.execute(&Parse, &mut context, &mut |context| {
+            seen = context.get("input").unwrap_or_default();
+            EventResult::Success(())
+        });
+
+        assert_ne!(seen, "plain");
+        assert_eq!(context.get::<String>("input").as_deref(), Some("plain"));
+    }
+
+    #[test]
+    fn finding_renders_as_escaped_json() {
+        let finding = finding(vec![
+            FuzzInput {
+                key: "input".to_string(),
+                value: FuzzValue::Text("' OR \"1\"=\"1".to_string()),
+            },
+            FuzzInput {
+                key: "limit".to_string(),
+                value: FuzzValue::I64(-1),
+            },
+        ]);
+
+        assert_eq!(
+            finding.to_json(),
+            concat!(
+                r#"{"seed":7,"attempt":3,"event":"Parse","fuzz_type":"SqlInjection","#,
+                r#""inputs":[{"key":"input","type":"String","payload":"' OR \"1\"=\"1"},"#,
+                r#"{"key":"limit","type":"i64","payload":"-1"}],"outcome":"panicked","#,
+                r#""error":"bad \"quote\"","oracle":"panic","detail":"panicked: bad \"quote\""}"#
+            )
+        );
+    }
+
+    #[test]
+    fn regression_test_sets_every_rebuildable_value() {
+        let finding = finding(vec![
+            FuzzInput {
+                key: "input".to_string(),
+                value: FuzzValue::Text("it's".to_string()),
+            },
+            FuzzInput {
+                key: "ratio".to_string(),
+                value: FuzzValue::F64(f64::NAN),
+            },
+            FuzzInput {
+                key: "order".to_string(),
+                value: FuzzValue::Custom {
+                    type_name: "Order".to_string(),
+                    debug: "Order { quantity: 0 }".to_string(),
+                },
+            },
+        ]);
+
+        let test = finding.to_regression_test("Parse");
+        assert!(test.starts_with("#[test]\nfn fuzz_parse_7_3() {\n"));
+        assert!(test.contains("    context.set(\"input\", String::from(\"it's\"));\n"));
+        assert!(test.contains("    context.set(\"ratio\", f64::NAN);\n"));
+        assert!(!test.contains("order") && !test.contains("TODO"));
+        assert!(test.contains("    let result = Parse.execute(&mut context);\n"));
+    }
+
+    #[test]
+    fn keys_created_by_fuzzing_are_removed_again() {
+        let fuzzer = fuzzer(1.0).with_seed(1);
+        let mut context = EventContext::new();
+
+        let mut seen = false;
+        fuzzer.execute(&Parse, &mut context, &mut |context| {
+            seen = context.has("input");
+            EventResult::Success(())
+        });
+
+        assert!(seen);
+        assert_eq!(context.keys().count(), 0);
+    }
+
+    #[test]
+    fn keys_the_event_overwrote_are_left_alone() {
+        let fuzzer = fuzzer(1.0).with_seed(1);
+        let mut context = input_context("plain");
+
+        let mut payload = String::new();
+        fuzzer.execute(&Parse, &mut context, &mut |context| {
+            // Writing an equal value back still counts as the event's own write
+            payload = context.get("input").unwrap();
+            context.set("input", payload.clone());
+            context.set("sanitized", true);
+            EventResult::Success(())
+        });
+
+        assert_ne!(payload, "plain");
+        assert_eq!(context.get::<String>("input"), Some(payload));
+        assert_eq!(context.get::<bool>("sanitized"), Some(true));
+    }
+
+    #[test]
+    fn with_restore_false_keeps_the_payload() {
+        let fuzzer = fuzzer(1.0).with_seed(1).with_restore(false);
+        let mut context = input_context("plain");
+
+        let mut seen = String::new();
+        fuzzer.execute(&Parse, &mut context, &mut |context| {
+            seen = context.get("input").unwrap_or_default();
+            EventResult::Success(())
+        });
+
+        assert_eq!(context.get::<String>("input"), Some(seen));
+        assert_eq!(context.keys().count(), 1);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Order {
+        quantity: u32,
+    }
+
+    /// Run an event once with `context`, returning the context it saw
+    fn seen_context(fuzzer: &FuzzingMiddleware, mut context: EventContext) -> EventContext {
+        let mut seen = EventContext::new();
+        fuzzer.execute(&Parse, &mut context, &mut |context| {
+            seen = context.clone();
+            EventResult::Success(())
+        });
+        seen
+    }
+
+    #[test]
+    fn type_preserving_injection_keeps_value_types() {
+        let fuzzer = fuzzer(1.0)
+            .with_target_keys(vec!["limit".into(), "admin".into(), "order".into(), "id".into()])
+            .with_hostile_values(vec![Order { quantity: 0 }, Order { quantity: u32::MAX }])
+            .with_seed(1);
+        let mut context = EventContext::new();
+        context.set("limit", 10i64);
+        context.set("admin", false);
+        context.set("order", Order { quantity: 1 });
+        context.set("id", std::time::Duration::from_secs(1));
+
+        let seen = seen_context(&fuzzer, context);
+        assert!(seen.is::<i64>("limit"));
+        assert_eq!(seen.get::<bool>("admin"), Some(true));
+        assert_ne!(seen.get::<Order>("order"), Some(Order { quantity: 1 }));
+        // Types without hostile values are left alone
+        assert_eq!(seen.get("id"), Some(std::time::Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn string_injection_replaces_values_of_any_type() {
+        let fuzzer = fuzzer(1.0)
+            .with_target_keys(vec!["limit".to_string()])
+            .with_type_preserving(false)
+            .with_seed(1);
+        let mut context = EventContext::new();
+        context.set("limit", 10i64);
+
+        let seen = seen_context(&fuzzer, context);
+        assert!(seen.is::<String>("limit"));
+    }
+
+    #[test]
+    fn key_kind_generates_values_for_absent_keys() {
+        let fuzzer = fuzzer(1.0)
+            .with_target_keys(vec!["retries".to_string()])
+            .with_key_kind("retries", ValueKind::U32)
+            .with_seed(1);
+
+        let seen = seen_context(&fuzzer, EventContext::new());
+        assert!(seen.is::<u32>("retries"));
+    }
+
+    #[test]
+    fn corpus_payloads_come_from_every_corpus() {
+        let fuzzer = fuzzer(1.0)
+            .with_fuzz_types(Vec::new())
+            .with_corpus(FuzzCorpus::new("ids", vec!["0000".to_string()]))
+            .with_corpus(FuzzCorpus::new("names", vec!["O'Brien".to_string()]))
+            .with_seed(3);
+
+        let mut payloads = seen_inputs(&fuzzer, 40);
+        payloads.sort();
+        payloads.dedup();
+        assert_eq!(payloads, ["0000", "O'Brien"]);
+    }
+
+    #[test]
+    fn empty_corpora_inject_an_empty_string() {
+        let fuzzer = fuzzer(1.0).with_fuzz_types(vec![FuzzType::Corpus]).with_seed(1);
+
+        assert_eq!(seen_inputs(&fuzzer, 1), [""]);
+    }
+
+    fn fuzzed_keys(fuzzer: &FuzzingMiddleware, context: EventContext) -> Vec<String> {
+        let mut context = context;
+        fuzzer.execute(&Parse, &mut context, &mut |_| EventResult::Success(()));
+        let findings = fuzzer.findings();
+        let finding = findings.last().expect("ExpectRejectionOracle reports every fuzzed call");
+        finding.inputs.iter().map(|input| input.key.clone()).collect()
+    }
+
+    #[test]
+    fn discovery_fuzzes_string_keys_matching_include_and_not_exclude() {
+        let fuzzer = FuzzingMiddleware::new(1.0)
+            .with_key_discovery(&["request.*", "user_*"], &["*token*"])
+            .with_oracle(ExpectRejectionOracle::all())
+            .with_logging(false)
+            .with_seed(1);
+        let mut context = EventContext::new();
+        context.set("request.body", "{}".to_string());
+        context.set("request.token", "secret".to_string());
+        context.set("request.size", 2u64);
+        context.set("user_name", "ada".to_string());
+        context.set("session", "abc".to_string());
+
+        assert_eq!(fuzzed_keys(&fuzzer, context), ["request.body", "user_name"]);
+    }
+
+    #[test]
+    fn discovery_without_include_patterns_fuzzes_every_string_key() {
+        let fuzzer = FuzzingMiddleware::new(1.0)
+            .with_key_discovery(&[], &["fallback:*"])
+            .with_oracle(ExpectRejectionOracle::all())
+            .with_logging(false)
+            .with_seed(1);
+        let mut context = EventContext::new();
+        context.set("comment", "hi".to_string());
+        context.set("fallback:error", "timeout".to_string());
+        context.set("count", 3i64);
+
+        assert_eq!(fuzzed_keys(&fuzzer, context), ["comment"]);
+    }
+}
@@ -0,0 +1,502 @@
+use crate::middleware::logging::LogLevel;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// A single log line produced by the logging middleware
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    pub event_name: String,
+    pub message: String,
+    /// Captured context values, already redacted
+    pub fields: Vec<(String, String)>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+}
+
+impl LogRecord {
+    pub fn new(level: LogLevel, event_name: &str, message: String) -> Self {
+        Self {
+            level,
+            event_name: event_name.to_string(),
+            message,
+            fields: Vec::new(),
+            timestamp_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        }
+    }
+
+    /// Attach captured context values
+    pub fn with_fields(mut self, fields: Vec<(String, String)>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Render the message followed by ` key=value` for each field
+    pub fn message_with_fields(&self) -> String {
+        let mut text = self.message.clone();
+        for (key, value) in &self.fields {
+            text.push_str(&format!(" {}={}", key, value));
+        }
+        text
+    }
+
+    /// Render as `[LEVEL] message key=value ...`
+    pub fn to_text(&self) -> String {
+        format!("[{}] {}", self.level.as_str(), self.message_with_fields())
+    }
+
+    /// Render as a single-line JSON object (without trailing newline)
+    pub fn to_json(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\":\"{}\"", escape_json(key), escape_json(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\"timestamp_ms\":{},\"level\":\"{}\",\"event\":\"{}\",\"message\":\"{}\",\"fields\":{{{}}}}}",
+            self.timestamp_ms,
+            self.level.as_str(),
+            escape_json(&self.event_name),
+            escape_json(&self.message),
+            fields
+        )
+    }
+}
+
+/// Destination for log records
+///
+/// Returning `Err` from [`write`](Self::write) makes `LoggingMiddleware`
+/// return `EventResult::MiddlewareFailure`.
+pub trait LogSink: Send + Sync {
+    fn write(&self, record: &LogRecord) -> Result<(), String>;
+}
+
+/// Writes text records to stdout (default sink)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl LogSink for StdoutSink {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        writeln!(std::io::stdout(), "{}", record.to_text())
+            .map_err(|e| format!("Logging infrastructure failure: {}", e))
+    }
+}
+
+/// Writes text records to stderr
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl LogSink for StderrSink {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        writeln!(std::io::stderr(), "{}", record.to_text())
+            .map_err(|e| format!("Logging infrastructure failure: {}", e))
+    }
+}
+
+/// Forwards records to the `log` or `tracing` facade (default sink when either feature is enabled)
+///
+/// With `tracing`, records become events with target `event_chains` and
+/// `component = "logging"`; with only `log`, the target is
+/// `event_chains::logging`.
+#[cfg(any(feature = "log", feature = "tracing"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FacadeSink;
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+impl LogSink for FacadeSink {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        crate::middleware::diagnostics::emit(
+            record.level,
+            "logging",
+            &record.event_name,
+            &record.message_with_fields(),
+        );
+        Ok(())
+    }
+}
+
+/// Collects records in memory, for asserting on log output in tests
+///
+/// Clones share the same buffer.
+///
+/// # Example
+///
+/// ```ignore
+/// let sink = MemorySink::new();
+/// let chain = EventChain::new()
+///     .middleware(LoggingMiddleware::info().with_sink(sink.clone()))
+///     .event(MyEvent);
+///
+/// chain.execute(&mut context);
+/// assert!(sink.contains("Completed event: MyEvent"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    records: Arc<Mutex<Vec<LogRecord>>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get all records written so far
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.records.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+
+    /// Get the messages of all records written so far
+    pub fn messages(&self) -> Vec<String> {
+        self.records
+            .lock()
+            .map(|r| r.iter().map(|record| record.message.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether any record's message contains `text`
+    pub fn contains(&self, text: &str) -> bool {
+        self.records
+            .lock()
+            .map(|r| r.iter().any(|record| record.message.contains(text)))
+            .unwrap_or(false)
+    }
+
+    /// Remove all records
+    pub fn clear(&self) {
+        if let Ok(mut records) = self.records.lock() {
+            records.clear();
+        }
+    }
+}
+
+impl LogSink for MemorySink {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        self.records
+            .lock()
+            .map_err(|_| "Logging infrastructure failure: memory sink lock poisoned".to_string())?
+            .push(record.clone());
+        Ok(())
+    }
+}
+
+/// Writes one JSON object per line to any writer (stdout, a file, a socket...)
+///
+/// # Example
+///
+/// ```ignore
+/// let sink = JsonLinesSink::new(File::create("events.jsonl")?);
+/// let logging = LoggingMiddleware::info().with_sink(sink);
+/// ```
+pub struct JsonLinesSink<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl JsonLinesSink<std::io::Stdout> {
+    /// Write JSON lines to stdout
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+impl<W: Write + Send> LogSink for JsonLinesSink<W> {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| "Logging infrastructure failure: writer lock poisoned".to_string())?;
+        writeln!(writer, "{}", record.to_json())
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("Logging infrastructure failure: {}", e))
+    }
+}
+
+/// Output format for [`FileSink`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// `[LEVEL] message`
+    Text,
+    /// One JSON object per line
+    JsonLines,
+}
+
+struct FileState {
+    file: File,
+    size: u64,
+}
+
+/// Appends records to a file, rotating it when it grows beyond a size limit
+///
+/// On rotation `app.log` is renamed to `app.log.1`, `app.log.1` to
+/// `app.log.2` and so on; files beyond `max_files` are deleted.
+///
+/// # Example
+///
+/// ```ignore
+/// // Rotate at 10 MB, keep 5 old files
+/// let sink = FileSink::new("logs/events.log")?
+///     .with_rotation(10 * 1024 * 1024, 5)
+///     .with_format(LogFormat::JsonLines);
+/// ```
+pub struct FileSink {
+    path: PathBuf,
+    state: Mutex<FileState>,
+    max_bytes: Option<u64>,
+    max_files: usize,
+    format: LogFormat,
+}
+
+impl FileSink {
+    /// Open (or create) `path` for appending, without rotation
+    pub fn new(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            state: Mutex::new(FileState { file, size }),
+            max_bytes: None,
+            max_files: 0,
+            format: LogFormat::Text,
+        })
+    }
+
+    /// Rotate when the file would exceed `max_bytes`, keeping up to `max_files` old files
+    pub fn with_rotation(mut self, max_bytes: u64, max_files: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.max_files = max_files;
+        self
+    }
+
+    /// Set the line format (default: [`LogFormat::Text`])
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self, state: &mut FileState) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            let oldest = self.rotated_path(self.max_files);
+            if oldest.exists() {
+                std::fs::remove_file(&oldest)?;
+            }
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        state.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        state.size = 0;
+        Ok(())
+    }
+}
+
+impl LogSink for FileSink {
+    fn write(&self, record: &LogRecord) -> Result<(), String> {
+        let line = match self.format {
+            LogFormat::Text => record.to_text(),
+            LogFormat::JsonLines => record.to_json(),
+        } + "\n";
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| "Logging infrastructure failure: file sink lock poisoned".to_string())?;
+
+        if let Some(max_bytes) = self.max_bytes
+            && state.size > 0
+            && state.size + line.len() as u64 > max_bytes
+        {
+            self.rotate(&mut state).map_err(|e| {
+                format!(
+                    "Logging infrastructure failure: could not rotate {}: {}",
+                    self.path.display(),
+                    e
+                )
+            })?;
+        }
+
+        state.file.write_all(line.as_bytes()).map_err(|e| {
+            format!(
+                "Logging infrastructure failure: could not write {}: {}",
+                self.path.display(),
+                e
+            )
+        })?;
+        state.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// Escape a string for inclusion in a JSON string literal
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_context::EventContext;
+    use crate::core::event_result::EventResult;
+    use crate::events::chainable_event::ChainableEvent;
+    use crate::events::event_middleware::EventMiddleware;
+    use crate::middleware::logging::LoggingMiddleware;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    struct BrokenSink;
+
+    impl LogSink for BrokenSink {
+        fn write(&self, _record: &LogRecord) -> Result<(), String> {
+            Err("disk full".to_string())
+        }
+    }
+
+    /// Writer whose output stays readable after it is moved into a sink
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn log_call(logging: &LoggingMiddleware, succeed: bool) -> EventResult<()> {
+        logging.execute(&Call, &mut EventContext::new(), &mut |_| {
+            if succeed {
+                EventResult::Success(())
+            } else {
+                EventResult::Failure("declined".to_string())
+            }
+        })
+    }
+
+    #[test]
+    fn sink_write_error_becomes_a_middleware_failure() {
+        let logging = LoggingMiddleware::info().with_sink(BrokenSink);
+
+        let result = log_call(&logging, true);
+        assert!(matches!(result, EventResult::MiddlewareFailure(ref e) if e == "disk full"));
+    }
+
+    #[test]
+    fn memory_sink_collects_records_from_the_middleware() {
+        let sink = MemorySink::new();
+        let logging = LoggingMiddleware::debug().with_sink(sink.clone());
+
+        log_call(&logging, true);
+        log_call(&logging, false);
+
+        assert_eq!(
+            sink.messages(),
+            [
+                "Starting event: Call",
+                "Completed event: Call",
+                "Starting event: Call",
+                "Failed event: Call - declined",
+            ]
+        );
+        let levels: Vec<_> = sink.records().iter().map(|r| r.level).collect();
+        assert_eq!(levels[3], LogLevel::Error);
+        assert!(sink.contains("declined"));
+
+        sink.clear();
+        assert!(sink.records().is_empty());
+    }
+
+    #[test]
+    fn json_lines_sink_writes_one_escaped_object_per_line() {
+        let buffer = SharedBuffer::default();
+        let sink = JsonLinesSink::new(buffer.clone());
+        let mut record = LogRecord::new(LogLevel::Warn, "Call", "said \"hi\"\n".to_string())
+            .with_fields(vec![("user".to_string(), "ada".to_string())]);
+        record.timestamp_ms = 1_700_000_000_000;
+
+        sink.write(&record).unwrap();
+        sink.write(&record).unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line = concat!(
+            r#"{"timestamp_ms":1700000000000,"level":"WARN","event":"Call","#,
+            r#""message":"said \"hi\"\n","fields":{"user":"ada"}}"#
+        );
+        assert_eq!(output, format!("{}\n{}\n", line, line));
+    }
+
+    #[test]
+    fn file_sink_rotates_and_keeps_max_files() {
+        let name = format!("event_chains_rotation_{}", std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("events.log");
+
+        // One record per file: every write after the first rotates
+        let sink = FileSink::new(&path).unwrap().with_rotation(1, 2);
+        for n in 1..=4 {
+            let record = LogRecord::new(LogLevel::Info, "Event", format!("record {}", n));
+            sink.write(&record).unwrap();
+        }
+
+        let read = |path: PathBuf| std::fs::read_to_string(path).unwrap();
+        assert!(read(path.clone()).contains("record 4"));
+        assert!(read(sink.rotated_path(1)).contains("record 3"));
+        assert!(read(sink.rotated_path(2)).contains("record 2"));
+        assert!(!sink.rotated_path(3).exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,6 +2,9 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::log_sink::{LogRecord, LogSink};
+use crate::middleware::redaction::Redactor;
+use std::sync::Arc;
 
 /// Logging levels for the middleware
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,6 +15,18 @@ pub enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    /// Upper-case name of the level, e.g. `"INFO"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
 /// Middleware that logs event execution with configurable log levels
 ///
 /// # Middleware Failures
@@ -20,16 +35,50 @@ pub enum LogLevel {
 /// this middleware returns `EventResult::MiddlewareFailure`, which will stop execution
 /// even in BestEffort mode, since logging infrastructure must be reliable.
 ///
+/// # Sinks
+///
+/// Records go to stdout by default, or to the `log`/`tracing` facade when one
+/// of those features is enabled. Use [`with_sink`](Self::with_sink) to send
+/// them to any [`LogSink`], such as [`StderrSink`](crate::middleware::log_sink::StderrSink),
+/// [`FileSink`](crate::middleware::log_sink::FileSink) (with rotation),
+/// [`JsonLinesSink`](crate::middleware::log_sink::JsonLinesSink) or
+/// [`MemorySink`](crate::middleware::log_sink::MemorySink) for tests.
+/// A sink write error becomes a `MiddlewareFailure`.
+///
+/// # Context Fields
+///
+/// Keys registered with [`with_context_keys`](Self::with_context_keys) are
+/// appended to the start and finish lines as `key=value` (or as a `fields`
+/// object in JSON sinks). Missing keys are omitted. Values of sensitive keys
+/// are masked by the [`Redactor`] set with [`with_redaction`](Self::with_redaction).
+///
 /// # Example
 ///
 /// ```ignore
 /// use event_chains::middleware::logging::{LoggingMiddleware, LogLevel};
+/// use event_chains::middleware::log_sink::{FileSink, LogFormat};
 ///
 /// let chain = EventChain::new()
 ///     .middleware(LoggingMiddleware::new(LogLevel::Info))
 ///     .event(MyEvent);
+///
+/// // JSON lines to a rotating file
+/// let sink = FileSink::new("events.log")?
+///     .with_rotation(10 * 1024 * 1024, 3)
+///     .with_format(LogFormat::JsonLines);
+/// let chain = EventChain::new()
+///     .middleware(LoggingMiddleware::info().with_sink(sink))
+///     .event(MyEvent);
+///
+/// // [INFO] Completed event: MyEvent request_id=42 password=[REDACTED]
+/// let logging = LoggingMiddleware::info()
+///     .with_context_keys(["request_id", "password"])
+///     .with_redaction(Redactor::new().with_key("password"));
 /// ```
 pub struct LoggingMiddleware {
+    sink: Arc<dyn LogSink>,
+    context_keys: Vec<String>,
+    redactor: Redactor,
     level: LogLevel,
     log_success: bool,
     log_failure: bool,
@@ -40,6 +89,9 @@ impl LoggingMiddleware {
     /// Create a new logging middleware with the specified log level
     pub fn new(level: LogLevel) -> Self {
         Self {
+            sink: default_sink(),
+            context_keys: Vec::new(),
+            redactor: Redactor::new(),
             level,
             log_success: true,
             log_failure: true,
@@ -50,6 +102,9 @@ impl LoggingMiddleware {
     /// Create a logging middleware that only logs errors
     pub fn errors_only() -> Self {
         Self {
+            sink: default_sink(),
+            context_keys: Vec::new(),
+            redactor: Redactor::new(),
             level: LogLevel::Error,
             log_success: false,
             log_failure: true,
@@ -79,6 +134,24 @@ impl LoggingMiddleware {
         self
     }
 
+    /// Send log records to `sink` instead of the default sink
+    pub fn with_sink<S: LogSink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Arc::new(sink);
+        self
+    }
+
+    /// Include the values of these context keys in start and finish lines
+    pub fn with_context_keys<'a>(mut self, keys: impl IntoIterator<Item = &'a str>) -> Self {
+        self.context_keys.extend(keys.into_iter().map(String::from));
+        self
+    }
+
+    /// Mask sensitive context values using `redactor`
+    pub fn with_redaction(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
     /// For testing: simulate a middleware failure
     #[doc(hidden)]
     pub fn with_simulated_failure(mut self) -> Self {
@@ -86,7 +159,13 @@ impl LoggingMiddleware {
         self
     }
 
-    fn log(&self, level: LogLevel, message: &str) -> Result<(), String> {
+    fn log(
+        &self,
+        level: LogLevel,
+        event_name: &str,
+        message: String,
+        context: &EventContext,
+    ) -> Result<(), String> {
         if !self.should_log(level) {
             return Ok(());
         }
@@ -96,14 +175,19 @@ impl LoggingMiddleware {
             return Err("Logging infrastructure failure: unable to write to log".to_string());
         }
 
-        let prefix = match level {
-            LogLevel::Debug => "DEBUG",
-            LogLevel::Info => "INFO",
-            LogLevel::Warn => "WARN",
-            LogLevel::Error => "ERROR",
-        };
-        println!("[{}] {}", prefix, message);
-        Ok(())
+        let record = LogRecord::new(level, event_name, message).with_fields(self.fields(context));
+        self.sink.write(&record)
+    }
+
+    fn fields(&self, context: &EventContext) -> Vec<(String, String)> {
+        self.context_keys
+            .iter()
+            .filter_map(|key| {
+                self.redactor
+                    .render(context, key)
+                    .map(|value| (key.clone(), value))
+            })
+            .collect()
     }
 
     fn should_log(&self, level: LogLevel) -> bool {
@@ -119,7 +203,8 @@ impl EventMiddleware for LoggingMiddleware {
         next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
     ) -> EventResult<()> {
         // Try to log start
-        if let Err(e) = self.log(LogLevel::Debug, &format!(" Starting event: {}", event.name())) {
+        let start_message = format!("Starting event: {}", event.name());
+        if let Err(e) = self.log(LogLevel::Debug, event.name(), start_message, context) {
             return EventResult::MiddlewareFailure(e);
         }
 
@@ -127,20 +212,27 @@ impl EventMiddleware for LoggingMiddleware {
 
         match &result {
             EventResult::Success(_) => {
-                if self.log_success {
-                    if let Err(e) = self.log(LogLevel::Info, &format!(" Completed event: {}", event.name())) {
-                        return EventResult::MiddlewareFailure(e);
-                    }
+                if self.log_success
+                    && let Err(e) = self.log(
+                        LogLevel::Info,
+                        event.name(),
+                        format!("Completed event: {}", event.name()),
+                        context,
+                    )
+                {
+                    return EventResult::MiddlewareFailure(e);
                 }
             }
             EventResult::Failure(err) | EventResult::MiddlewareFailure(err) => {
-                if self.log_failure {
-                    if let Err(e) = self.log(
+                if self.log_failure
+                    && let Err(e) = self.log(
                         LogLevel::Error,
-                        &format!(" Failed event: {} - {}", event.name(), err),
-                    ) {
-                        return EventResult::MiddlewareFailure(e);
-                    }
+                        event.name(),
+                        format!("Failed event: {} - {}", event.name(), err),
+                        context,
+                    )
+                {
+                    return EventResult::MiddlewareFailure(e);
                 }
             }
         }
@@ -154,3 +246,13 @@ impl Default for LoggingMiddleware {
         Self::info()
     }
 }
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+fn default_sink() -> Arc<dyn LogSink> {
+    Arc::new(crate::middleware::log_sink::FacadeSink)
+}
+
+#[cfg(not(any(feature = "log", feature = "tracing")))]
+fn default_sink() -> Arc<dyn LogSink> {
+    Arc::new(crate::middleware::log_sink::StdoutSink)
+}
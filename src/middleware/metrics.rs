@@ -2,8 +2,251 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use std::fmt;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Number of sub-buckets per power of two (as a power of two)
+const SUB_BUCKET_BITS: u32 = 4;
+const SUB_BUCKET_COUNT: u64 = 1 << SUB_BUCKET_BITS;
+
+/// Resolution of the rolling windows, in seconds
+const WINDOW_SLOT_SECS: u64 = 10;
+
+/// Number of rolling window slots kept per event (15 minutes)
+const WINDOW_SLOTS: u64 = 90;
+
+/// Default Prometheus histogram bucket boundaries, in seconds
+const PROMETHEUS_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Default read and write timeout for connections to the Prometheus endpoint
+pub const PROMETHEUS_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Log-linear latency histogram with bounded memory
+///
+/// Values below 16 are recorded exactly. Larger values fall into one of 16
+/// sub-buckets per power of two, so any recorded value is reported with a
+/// relative error of at most 1/16 (6.25%). The histogram never holds more
+/// than ~1000 counters, regardless of how many values are recorded.
+#[derive(Clone, Default)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+    min: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn bucket_index(value: u64) -> usize {
+        if value < SUB_BUCKET_COUNT {
+            return value as usize;
+        }
+        let exponent = 63 - value.leading_zeros();
+        let shift = exponent - SUB_BUCKET_BITS;
+        let sub_bucket = (value >> shift) & (SUB_BUCKET_COUNT - 1);
+        ((exponent - SUB_BUCKET_BITS + 1) as u64 * SUB_BUCKET_COUNT + sub_bucket) as usize
+    }
+
+    /// Highest value that maps to the bucket at `index`
+    fn bucket_upper_bound(index: usize) -> u64 {
+        let index = index as u64;
+        if index < SUB_BUCKET_COUNT {
+            return index;
+        }
+        let exponent = (index / SUB_BUCKET_COUNT) as u32 + SUB_BUCKET_BITS - 1;
+        let shift = exponent - SUB_BUCKET_BITS;
+        let sub_bucket = index % SUB_BUCKET_COUNT;
+        let lower = (SUB_BUCKET_COUNT + sub_bucket) << shift;
+        lower.saturating_add((1u64 << shift) - 1)
+    }
+
+    /// Record a single value
+    pub fn record(&mut self, value: u64) {
+        let index = Self::bucket_index(value);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+
+        if self.total == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.total += 1;
+    }
+
+    /// Number of recorded values
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Value at the given quantile (0.0 - 1.0), or 0 if the histogram is empty
+    ///
+    /// The result is the upper bound of the bucket containing the quantile,
+    /// clamped to the smallest and largest recorded values.
+    pub fn value_at_quantile(&self, quantile: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+
+        let rank = ((quantile.clamp(0.0, 1.0) * self.total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Self::bucket_upper_bound(index).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Iterate over non-empty buckets as `(upper_bound, count)` pairs in ascending order
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(index, count)| (Self::bucket_upper_bound(index), *count))
+    }
+}
+
+impl fmt::Debug for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LatencyHistogram")
+            .field("count", &self.total)
+            .field("p50", &self.value_at_quantile(0.5))
+            .field("p99", &self.value_at_quantile(0.99))
+            .finish()
+    }
+}
+
+/// Counters for one [`WINDOW_SLOT_SECS`] time slot
+#[derive(Debug, Clone, Copy, Default)]
+struct WindowSlot {
+    slot: u64,
+    executions: u64,
+    successes: u64,
+    failures: u64,
+    total_duration_micros: u64,
+}
+
+/// Ring buffer of time slots covering the last 15 minutes
+#[derive(Debug, Clone)]
+struct RollingCounters {
+    slots: Vec<WindowSlot>,
+}
+
+impl RollingCounters {
+    fn new() -> Self {
+        Self {
+            slots: vec![WindowSlot::default(); WINDOW_SLOTS as usize],
+        }
+    }
+
+    fn record(&mut self, now_secs: u64, duration_micros: u64, success: bool) {
+        let slot_id = now_secs / WINDOW_SLOT_SECS;
+        let slot = &mut self.slots[(slot_id % WINDOW_SLOTS) as usize];
+
+        // Reuse a slot left over from an earlier lap of the ring
+        if slot.slot != slot_id {
+            *slot = WindowSlot {
+                slot: slot_id,
+                ..Default::default()
+            };
+        }
+
+        slot.executions += 1;
+        if success {
+            slot.successes += 1;
+        } else {
+            slot.failures += 1;
+        }
+        slot.total_duration_micros += duration_micros;
+    }
+
+    fn snapshot(&self, event_name: &str, now_secs: u64, window: Duration) -> WindowedMetrics {
+        let current = now_secs / WINDOW_SLOT_SECS;
+        let slots = window
+            .as_secs()
+            .div_ceil(WINDOW_SLOT_SECS)
+            .clamp(1, WINDOW_SLOTS);
+        let oldest = (current + 1).saturating_sub(slots);
+
+        let mut metrics = WindowedMetrics {
+            event_name: event_name.to_string(),
+            window: Duration::from_secs(slots * WINDOW_SLOT_SECS),
+            total_executions: 0,
+            successful_executions: 0,
+            failed_executions: 0,
+            total_duration_micros: 0,
+        };
+
+        for slot in self.slots.iter().filter(|s| s.slot >= oldest && s.slot <= current) {
+            metrics.total_executions += slot.executions;
+            metrics.successful_executions += slot.successes;
+            metrics.failed_executions += slot.failures;
+            metrics.total_duration_micros += slot.total_duration_micros;
+        }
+
+        metrics
+    }
+}
+
+/// Statistics for a single event over a recent time window
+///
+/// Windows have a resolution of 10 seconds and cover at most the last
+/// 15 minutes; `window` holds the span actually covered.
+#[derive(Debug, Clone)]
+pub struct WindowedMetrics {
+    pub event_name: String,
+    pub window: Duration,
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub failed_executions: u64,
+    pub total_duration_micros: u64,
+}
+
+impl WindowedMetrics {
+    /// Get the average execution time in microseconds
+    pub fn avg_duration_micros(&self) -> u64 {
+        self.total_duration_micros
+            .checked_div(self.total_executions)
+            .unwrap_or(0)
+    }
+
+    /// Get the success rate as a percentage (0.0 - 100.0)
+    pub fn success_rate(&self) -> f64 {
+        if self.total_executions == 0 {
+            0.0
+        } else {
+            (self.successful_executions as f64 / self.total_executions as f64) * 100.0
+        }
+    }
+}
+
+/// Cumulative totals and rolling window counters for one event
+struct EventRecord {
+    totals: EventMetrics,
+    /// What was recorded since the last [`MetricsMiddleware::drain`]
+    undrained: EventMetrics,
+    recent: RollingCounters,
+}
 
 /// Statistics for a single event
 #[derive(Debug, Clone)]
@@ -12,9 +255,15 @@ pub struct EventMetrics {
     pub total_executions: u64,
     pub successful_executions: u64,
     pub failed_executions: u64,
+    /// Failures that were `MiddlewareFailure` (also counted in `failed_executions`)
+    pub middleware_failed_executions: u64,
     pub total_duration_micros: u64,
     pub min_duration_micros: u64,
     pub max_duration_micros: u64,
+    /// Distribution of execution times in microseconds
+    pub latency: LatencyHistogram,
+    /// Executions per Prometheus bucket (not cumulative), counted exactly
+    prometheus_buckets: [u64; PROMETHEUS_BUCKETS.len()],
 }
 
 impl EventMetrics {
@@ -24,32 +273,73 @@ impl EventMetrics {
             total_executions: 0,
             successful_executions: 0,
             failed_executions: 0,
+            middleware_failed_executions: 0,
             total_duration_micros: 0,
             min_duration_micros: u64::MAX,
             max_duration_micros: 0,
+            latency: LatencyHistogram::new(),
+            prometheus_buckets: [0; PROMETHEUS_BUCKETS.len()],
         }
     }
 
-    fn record(&mut self, duration_micros: u64, success: bool) {
+    fn record(&mut self, duration_micros: u64, result: &EventResult<()>) {
         self.total_executions += 1;
-        if success {
-            self.successful_executions += 1;
-        } else {
-            self.failed_executions += 1;
+        match result {
+            EventResult::Success(_) => self.successful_executions += 1,
+            EventResult::Failure(_) => self.failed_executions += 1,
+            EventResult::MiddlewareFailure(_) => {
+                self.failed_executions += 1;
+                self.middleware_failed_executions += 1;
+            }
         }
 
         self.total_duration_micros += duration_micros;
         self.min_duration_micros = self.min_duration_micros.min(duration_micros);
         self.max_duration_micros = self.max_duration_micros.max(duration_micros);
+        self.latency.record(duration_micros);
+        if let Some(index) = PROMETHEUS_BUCKETS
+            .iter()
+            .position(|le| duration_micros <= le_micros(*le))
+        {
+            self.prometheus_buckets[index] += 1;
+        }
     }
 
     /// Get the average execution time in microseconds
     pub fn avg_duration_micros(&self) -> u64 {
-        if self.total_executions == 0 {
-            0
-        } else {
-            self.total_duration_micros / self.total_executions
-        }
+        self.total_duration_micros
+            .checked_div(self.total_executions)
+            .unwrap_or(0)
+    }
+
+    /// Get the execution time at the given percentile (0.0 - 100.0) in microseconds
+    pub fn percentile_micros(&self, percentile: f64) -> u64 {
+        self.latency.value_at_quantile(percentile / 100.0)
+    }
+
+    /// Get the median execution time in microseconds
+    pub fn p50_micros(&self) -> u64 {
+        self.percentile_micros(50.0)
+    }
+
+    /// Get the 90th percentile execution time in microseconds
+    pub fn p90_micros(&self) -> u64 {
+        self.percentile_micros(90.0)
+    }
+
+    /// Get the 99th percentile execution time in microseconds
+    pub fn p99_micros(&self) -> u64 {
+        self.percentile_micros(99.0)
+    }
+
+    /// Get the 99.9th percentile execution time in microseconds
+    pub fn p999_micros(&self) -> u64 {
+        self.percentile_micros(99.9)
+    }
+
+    /// Get the number of event (business logic) failures
+    pub fn event_failed_executions(&self) -> u64 {
+        self.failed_executions - self.middleware_failed_executions
     }
 
     /// Get the success rate as a percentage (0.0 - 100.0)
@@ -88,10 +378,39 @@ impl EventMetrics {
 /// // Later, retrieve metrics
 /// metrics_clone.print_summary();
 /// let event_stats = metrics_clone.get_metrics("MyEvent");
+/// if let Some(stats) = event_stats {
+///     println!("p99: {}µs", stats.p99_micros());
+/// }
+/// ```
+///
+/// # Rolling Windows
+///
+/// Besides cumulative totals, recent activity is kept per event for the last
+/// 15 minutes at 10 second resolution, see
+/// [`get_windowed_metrics`](Self::get_windowed_metrics). Push-based exporters
+/// can use [`drain`](Self::drain) to read what was recorded since the
+/// previous drain, without resetting the cumulative totals.
+///
+/// # Prometheus
+///
+/// [`render_prometheus`](Self::render_prometheus) renders all metrics in the
+/// Prometheus text exposition format, and
+/// [`serve_prometheus`](Self::serve_prometheus) exposes them on a local
+/// HTTP endpoint for scraping:
+///
+/// ```ignore
+/// let metrics = MetricsMiddleware::new().with_chain_name("checkout");
+/// let server = metrics.serve_prometheus(9898)?;
+/// // curl http://127.0.0.1:9898/metrics
+/// server.shutdown();
 /// ```
 #[derive(Clone)]
 pub struct MetricsMiddleware {
-    metrics: Arc<Mutex<std::collections::HashMap<String, EventMetrics>>>,
+    metrics: Arc<Mutex<std::collections::HashMap<String, EventRecord>>>,
+    started: Instant,
+    clock: SharedClock,
+    chain_name: Option<String>,
+    prometheus_timeout: Duration,
     fail_on_lock_error: bool,  // For BestEffort mode: fail if can't record metrics
 }
 
@@ -100,6 +419,10 @@ impl MetricsMiddleware {
     pub fn new() -> Self {
         Self {
             metrics: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            started: Instant::now(),
+            clock: SystemClock::shared(),
+            chain_name: None,
+            prometheus_timeout: PROMETHEUS_IO_TIMEOUT,
             fail_on_lock_error: true,  // Default: fail if metrics infrastructure broken
         }
     }
@@ -113,13 +436,35 @@ impl MetricsMiddleware {
         self
     }
 
+    /// Measure durations and rolling windows with `clock` instead of the system clock
+    ///
+    /// Rolling windows restart from the clock's current time.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self.started = self.clock.now();
+        self
+    }
+
+    /// Set the chain name used as the `chain` label in Prometheus output
+    pub fn with_chain_name(mut self, name: &str) -> Self {
+        self.chain_name = Some(name.to_string());
+        self
+    }
+
+    /// Set the read and write timeout for connections to the Prometheus endpoint
+    /// (default: [`PROMETHEUS_IO_TIMEOUT`])
+    pub fn with_prometheus_timeout(mut self, timeout: Duration) -> Self {
+        self.prometheus_timeout = timeout;
+        self
+    }
+
     /// Get metrics for a specific event
     pub fn get_metrics(&self, event_name: &str) -> Option<EventMetrics> {
         self.metrics
             .lock()
             .ok()?
             .get(event_name)
-            .cloned()
+            .map(|record| record.totals.clone())
     }
 
     /// Get all collected metrics
@@ -127,17 +472,82 @@ impl MetricsMiddleware {
         self.metrics
             .lock()
             .ok()
-            .map(|m| m.values().cloned().collect())
+            .map(|m| m.values().map(|record| record.totals.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get metrics for a specific event over the most recent `window`
+    ///
+    /// Windows have a 10 second resolution and are capped at 15 minutes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for minutes in [1, 5, 15] {
+    ///     let window = Duration::from_secs(minutes * 60);
+    ///     if let Some(m) = metrics.get_windowed_metrics("ChargeCard", window) {
+    ///         println!("{}m success rate: {:.1}%", minutes, m.success_rate());
+    ///     }
+    /// }
+    /// ```
+    pub fn get_windowed_metrics(
+        &self,
+        event_name: &str,
+        window: Duration,
+    ) -> Option<WindowedMetrics> {
+        let now_secs = self.uptime_secs();
+        self.metrics
+            .lock()
+            .ok()?
+            .get(event_name)
+            .map(|record| record.recent.snapshot(event_name, now_secs, window))
+    }
+
+    /// Get metrics for all events over the most recent `window`
+    pub fn get_all_windowed_metrics(&self, window: Duration) -> Vec<WindowedMetrics> {
+        let now_secs = self.uptime_secs();
+        self.metrics
+            .lock()
+            .ok()
+            .map(|m| {
+                m.iter()
+                    .map(|(name, record)| record.recent.snapshot(name, now_secs, window))
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
-    /// Reset all metrics
+    /// Atomically return the metrics recorded since the previous drain
+    ///
+    /// Intended for push-based exporters. Events without executions since the
+    /// previous drain are left out. Cumulative totals (and so the Prometheus
+    /// counters) and rolling windows are not affected.
+    pub fn drain(&self) -> Vec<EventMetrics> {
+        let Ok(mut metrics) = self.metrics.lock() else {
+            return Vec::new();
+        };
+
+        metrics
+            .iter_mut()
+            .filter(|(_, record)| record.undrained.total_executions > 0)
+            .map(|(name, record)| {
+                std::mem::replace(&mut record.undrained, EventMetrics::new(name.clone()))
+            })
+            .collect()
+    }
+
+    /// Reset all metrics, including rolling windows
     pub fn reset(&self) {
         if let Ok(mut metrics) = self.metrics.lock() {
             metrics.clear();
         }
     }
 
+    /// Seconds since this middleware was created, used to assign rolling-window slots
+    fn uptime_secs(&self) -> u64 {
+        self.clock.now().duration_since(self.started).as_secs()
+    }
+
     /// Print a summary of all metrics to stdout
     pub fn print_summary(&self) {
         let Ok(metrics) = self.metrics.lock() else {
@@ -146,16 +556,17 @@ impl MetricsMiddleware {
         };
 
         println!("\n=== Event Metrics Summary ===");
-        println!("{:<25} {:>10} {:>10} {:>10} {:>12} {:>12} {:>12} {:>10}",
-                 "Event", "Total", "Success", "Failed", "Avg (µs)", "Min (µs)", "Max (µs)", "Success %");
-        println!("{}", "-".repeat(115));
+        println!("{:<25} {:>10} {:>10} {:>10} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>10}",
+                 "Event", "Total", "Success", "Failed", "Avg (µs)", "Min (µs)", "Max (µs)",
+                 "p50 (µs)", "p90 (µs)", "p99 (µs)", "p99.9 (µs)", "Success %");
+        println!("{}", "-".repeat(167));
 
-        let mut sorted: Vec<_> = metrics.values().collect();
+        let mut sorted: Vec<_> = metrics.values().map(|record| &record.totals).collect();
         sorted.sort_by(|a, b| a.event_name.cmp(&b.event_name));
 
         for metric in sorted {
             println!(
-                "{:<25} {:>10} {:>10} {:>10} {:>12} {:>12} {:>12} {:>9.1}%",
+                "{:<25} {:>10} {:>10} {:>10} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>9.1}%",
                 metric.event_name,
                 metric.total_executions,
                 metric.successful_executions,
@@ -163,11 +574,218 @@ impl MetricsMiddleware {
                 metric.avg_duration_micros(),
                 metric.min_duration_micros,
                 metric.max_duration_micros,
+                metric.p50_micros(),
+                metric.p90_micros(),
+                metric.p99_micros(),
+                metric.p999_micros(),
                 metric.success_rate()
             );
         }
         println!();
     }
+
+    /// Render all metrics in the Prometheus text exposition format (version 0.0.4)
+    ///
+    /// Exposes, labeled by `event` (and `chain` if a chain name is set):
+    /// - `event_chains_executions_total` (counter)
+    /// - `event_chains_successes_total` (counter)
+    /// - `event_chains_failures_total` (counter, with `kind="event"` or `kind="middleware"`)
+    /// - `event_chains_duration_seconds` (histogram)
+    ///
+    /// Histogram bucket counts are exact: every execution is counted in the
+    /// first bucket whose bound it does not exceed, independent of the
+    /// coarser [`LatencyHistogram`] buckets.
+    pub fn render_prometheus(&self) -> String {
+        let mut sorted = self.get_all_metrics();
+        sorted.sort_by(|a, b| a.event_name.cmp(&b.event_name));
+
+        let mut out = String::new();
+        let labels = |metric: &EventMetrics| match &self.chain_name {
+            Some(chain) => format!(
+                "chain=\"{}\",event=\"{}\"",
+                escape_label_value(chain),
+                escape_label_value(&metric.event_name)
+            ),
+            None => format!("event=\"{}\"", escape_label_value(&metric.event_name)),
+        };
+
+        out.push_str("# HELP event_chains_executions_total Total number of event executions.\n");
+        out.push_str("# TYPE event_chains_executions_total counter\n");
+        for metric in &sorted {
+            write_sample(
+                &mut out,
+                "event_chains_executions_total",
+                &labels(metric),
+                metric.total_executions,
+            );
+        }
+
+        out.push_str("# HELP event_chains_successes_total Number of successful event executions.\n");
+        out.push_str("# TYPE event_chains_successes_total counter\n");
+        for metric in &sorted {
+            write_sample(
+                &mut out,
+                "event_chains_successes_total",
+                &labels(metric),
+                metric.successful_executions,
+            );
+        }
+
+        out.push_str("# HELP event_chains_failures_total Number of failed event executions by failure kind.\n");
+        out.push_str("# TYPE event_chains_failures_total counter\n");
+        for metric in &sorted {
+            let labels = labels(metric);
+            let event_labels = format!("{},kind=\"event\"", labels);
+            let middleware_labels = format!("{},kind=\"middleware\"", labels);
+            write_sample(
+                &mut out,
+                "event_chains_failures_total",
+                &event_labels,
+                metric.event_failed_executions(),
+            );
+            write_sample(
+                &mut out,
+                "event_chains_failures_total",
+                &middleware_labels,
+                metric.middleware_failed_executions,
+            );
+        }
+
+        out.push_str("# HELP event_chains_duration_seconds Event execution time in seconds.\n");
+        out.push_str("# TYPE event_chains_duration_seconds histogram\n");
+        for metric in &sorted {
+            let labels = labels(metric);
+            let mut count = 0;
+            for (le, executions) in PROMETHEUS_BUCKETS.iter().zip(metric.prometheus_buckets) {
+                count += executions;
+                let bucket_labels = format!("{},le=\"{}\"", labels, le);
+                write_sample(
+                    &mut out,
+                    "event_chains_duration_seconds_bucket",
+                    &bucket_labels,
+                    count,
+                );
+            }
+            let inf_labels = format!("{},le=\"+Inf\"", labels);
+            let sum_seconds = metric.total_duration_micros as f64 / 1_000_000.0;
+            write_sample(
+                &mut out,
+                "event_chains_duration_seconds_bucket",
+                &inf_labels,
+                metric.total_executions,
+            );
+            write_sample(&mut out, "event_chains_duration_seconds_sum", &labels, sum_seconds);
+            write_sample(
+                &mut out,
+                "event_chains_duration_seconds_count",
+                &labels,
+                metric.total_executions,
+            );
+        }
+
+        out
+    }
+
+    /// Serve [`render_prometheus`](Self::render_prometheus) output over HTTP on `127.0.0.1:port`
+    ///
+    /// Spawns a background thread that answers every request with the current
+    /// metrics. Use port `0` to pick a free port. The returned handle reports
+    /// the bound address and stops the server; dropping it leaves the server
+    /// running.
+    ///
+    /// This is a minimal endpoint intended for local scraping; it only binds to
+    /// localhost and handles one connection at a time. Clients that stay silent
+    /// are dropped after the timeout set with
+    /// [`with_prometheus_timeout`](Self::with_prometheus_timeout).
+    pub fn serve_prometheus(&self, port: u16) -> std::io::Result<PrometheusServer> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let addr = listener.local_addr()?;
+        let metrics = self.clone();
+        let timeout = Some(self.prometheus_timeout);
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+
+        let thread = std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                if stream.set_read_timeout(timeout).is_err()
+                    || stream.set_write_timeout(timeout).is_err()
+                {
+                    continue;
+                }
+
+                // Read (and ignore) the request; every path returns the metrics
+                let mut buffer = [0u8; 1024];
+                if stream.read(&mut buffer).is_err() {
+                    continue;
+                }
+
+                let body = metrics.render_prometheus();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\n\
+                     Content-Type: text/plain; version=0.0.4\r\n\
+                     Content-Length: {}\r\n\
+                     Connection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Ok(PrometheusServer {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// Handle to the endpoint started by [`MetricsMiddleware::serve_prometheus`]
+pub struct PrometheusServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PrometheusServer {
+    /// Address the endpoint is bound to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting connections and wait for the server thread to exit
+    ///
+    /// A request being answered is finished first, which takes at most the
+    /// configured timeout per read and write.
+    pub fn shutdown(mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Prometheus bucket bound in whole microseconds
+fn le_micros(le: f64) -> u64 {
+    (le * 1_000_000.0).round() as u64
+}
+
+/// Append a single `name{labels} value` line
+fn write_sample(out: &mut String, name: &str, labels: &str, value: impl fmt::Display) {
+    let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+}
+
+/// Escape a Prometheus label value (backslash, double quote and newline)
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 impl EventMiddleware for MetricsMiddleware {
@@ -177,17 +795,25 @@ impl EventMiddleware for MetricsMiddleware {
         context: &mut EventContext,
         next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
     ) -> EventResult<()> {
-        let start = Instant::now();
+        let start = self.clock.now();
         let result = next(context);
-        let duration = start.elapsed();
+        let duration = self.clock.now().duration_since(start);
 
         // Try to record metrics
+        let now_secs = self.uptime_secs();
         let record_result = self.metrics.lock().map(|mut metrics| {
-            let event_metrics = metrics
+            let record = metrics
                 .entry(event.name().to_string())
-                .or_insert_with(|| EventMetrics::new(event.name().to_string()));
+                .or_insert_with(|| EventRecord {
+                    totals: EventMetrics::new(event.name().to_string()),
+                    undrained: EventMetrics::new(event.name().to_string()),
+                    recent: RollingCounters::new(),
+                });
 
-            event_metrics.record(duration.as_micros() as u64, result.is_success());
+            let duration_micros = duration.as_micros() as u64;
+            record.totals.record(duration_micros, &result);
+            record.undrained.record(duration_micros, &result);
+            record.recent.record(now_secs, duration_micros, result.is_success());
         });
 
         // If we failed to record metrics and fail_on_lock_error is true, return middleware failure
@@ -206,3 +832,225 @@ impl Default for MetricsMiddleware {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::clock::ManualClock;
+
+    #[test]
+    fn prometheus_server_survives_silent_client_and_shuts_down() {
+        let timeout = Duration::from_millis(50);
+        let server = MetricsMiddleware::new()
+            .with_prometheus_timeout(timeout)
+            .serve_prometheus(0)
+            .unwrap();
+        let _silent = TcpStream::connect(server.addr()).unwrap();
+
+        let mut client = TcpStream::connect(server.addr()).unwrap();
+        client.set_read_timeout(Some(PROMETHEUS_IO_TIMEOUT)).unwrap();
+        client.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+        let addr = server.addr();
+        server.shutdown();
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn small_values_get_exact_buckets() {
+        for value in 0..SUB_BUCKET_COUNT {
+            let index = LatencyHistogram::bucket_index(value);
+            assert_eq!(index as u64, value);
+            assert_eq!(LatencyHistogram::bucket_upper_bound(index), value);
+        }
+    }
+
+    #[test]
+    fn bucket_bounds_are_contiguous_and_within_relative_error() {
+        let mut value = SUB_BUCKET_COUNT;
+        while value < 1 << 40 {
+            let index = LatencyHistogram::bucket_index(value);
+            let upper = LatencyHistogram::bucket_upper_bound(index);
+            assert!(upper >= value);
+            assert!((upper - value) as f64 <= value as f64 / SUB_BUCKET_COUNT as f64);
+            assert_eq!(LatencyHistogram::bucket_index(upper), index);
+            assert_eq!(LatencyHistogram::bucket_index(upper + 1), index + 1);
+            value = value * 17 / 16 + 1;
+        }
+
+        let index = LatencyHistogram::bucket_index(u64::MAX);
+        assert_eq!(LatencyHistogram::bucket_upper_bound(index), u64::MAX);
+    }
+
+    #[test]
+    fn quantiles_are_clamped_to_recorded_values() {
+        let mut histogram = LatencyHistogram::new();
+        for value in 1..=100 {
+            histogram.record(value);
+        }
+
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.value_at_quantile(0.0), 1);
+        assert_eq!(histogram.value_at_quantile(0.5), 51);
+        assert_eq!(histogram.value_at_quantile(1.0), 100);
+        assert_eq!(histogram.buckets().map(|(_, count)| count).sum::<u64>(), 100);
+        assert_eq!(LatencyHistogram::new().value_at_quantile(0.5), 0);
+    }
+
+    #[test]
+    fn histogram_memory_stays_bounded_for_any_value() {
+        let mut histogram = LatencyHistogram::new();
+        let mut value = 1u64;
+        while let Some(next) = value.checked_mul(3) {
+            histogram.record(value);
+            value = next;
+        }
+        histogram.record(u64::MAX);
+
+        assert!(histogram.counts.len() <= 64 * SUB_BUCKET_COUNT as usize);
+        let bounds: Vec<u64> = histogram.buckets().map(|(upper, _)| upper).collect();
+        assert!(bounds.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(histogram.value_at_quantile(1.0), u64::MAX);
+    }
+
+    #[test]
+    fn quantiles_of_large_values_stay_within_relative_error() {
+        let mut histogram = LatencyHistogram::new();
+        for value in 1..=10_000u64 {
+            histogram.record(value * 100);
+        }
+
+        for (quantile, exact) in [(0.5, 500_000.0), (0.9, 900_000.0), (0.99, 990_000.0)] {
+            let reported = histogram.value_at_quantile(quantile) as f64;
+            assert!(reported >= exact, "q{} reported {} < {}", quantile, reported, exact);
+            assert!(reported <= exact * (1.0 + 1.0 / SUB_BUCKET_COUNT as f64));
+        }
+        assert_eq!(histogram.value_at_quantile(1.0), 1_000_000);
+    }
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    /// Record one call that takes `duration`
+    fn call(metrics: &MetricsMiddleware, clock: &ManualClock, duration: Duration, succeed: bool) {
+        metrics.execute(&Call, &mut EventContext::new(), &mut |_| {
+            clock.advance(duration);
+            if succeed {
+                EventResult::Success(())
+            } else {
+                EventResult::Failure("down".to_string())
+            }
+        });
+    }
+
+    #[test]
+    fn drain_returns_deltas_and_leaves_totals_alone() {
+        let clock = ManualClock::new();
+        let metrics = MetricsMiddleware::new().with_clock(clock.clone());
+        call(&metrics, &clock, Duration::from_millis(1), true);
+        call(&metrics, &clock, Duration::from_millis(3), false);
+
+        let drained = metrics.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].total_executions, 2);
+        assert_eq!(drained[0].failed_executions, 1);
+        assert!(metrics.drain().is_empty());
+
+        call(&metrics, &clock, Duration::from_millis(2), true);
+        let drained = metrics.drain();
+        assert_eq!(drained[0].total_executions, 1);
+        assert_eq!(drained[0].min_duration_micros, 2000);
+
+        let totals = metrics.get_metrics("Call").unwrap();
+        assert_eq!(totals.total_executions, 3);
+        assert_eq!(totals.min_duration_micros, 1000);
+        let exposition = metrics.render_prometheus();
+        assert!(exposition.contains("event_chains_executions_total{event=\"Call\"} 3"));
+    }
+
+    #[test]
+    fn windowed_metrics_only_cover_recent_slots() {
+        let clock = ManualClock::new();
+        let metrics = MetricsMiddleware::new().with_clock(clock.clone());
+        call(&metrics, &clock, Duration::ZERO, false);
+        clock.advance(Duration::from_secs(120));
+        call(&metrics, &clock, Duration::ZERO, true);
+        call(&metrics, &clock, Duration::ZERO, true);
+
+        let minute = metrics.get_windowed_metrics("Call", Duration::from_secs(60)).unwrap();
+        assert_eq!(minute.window, Duration::from_secs(60));
+        assert_eq!(minute.total_executions, 2);
+        assert_eq!(minute.success_rate(), 100.0);
+
+        let quarter = metrics.get_windowed_metrics("Call", Duration::from_secs(900)).unwrap();
+        assert_eq!(quarter.total_executions, 3);
+        assert_eq!(quarter.failed_executions, 1);
+    }
+
+    #[test]
+    fn windowed_metrics_forget_slots_older_than_fifteen_minutes() {
+        let clock = ManualClock::new();
+        let metrics = MetricsMiddleware::new().with_clock(clock.clone());
+        call(&metrics, &clock, Duration::ZERO, true);
+        // A full lap of the ring later, the old slot is reused, not added to
+        clock.advance(Duration::from_secs(WINDOW_SLOTS * WINDOW_SLOT_SECS));
+        call(&metrics, &clock, Duration::ZERO, true);
+
+        let window = metrics.get_windowed_metrics("Call", Duration::from_secs(3600)).unwrap();
+        assert_eq!(window.window, Duration::from_secs(900));
+        assert_eq!(window.total_executions, 1);
+        assert_eq!(metrics.get_metrics("Call").unwrap().total_executions, 2);
+    }
+
+    #[test]
+    fn prometheus_buckets_count_executions_straddling_a_histogram_bucket() {
+        let clock = ManualClock::new();
+        let metrics = MetricsMiddleware::new().with_clock(clock.clone());
+        // 4990µs and 5100µs share the LatencyHistogram bucket 4864..=5119µs,
+        // which straddles the 5ms Prometheus bound
+        call(&metrics, &clock, Duration::from_micros(4990), true);
+        call(&metrics, &clock, Duration::from_micros(5000), true);
+        call(&metrics, &clock, Duration::from_micros(5100), true);
+
+        let exposition = metrics.render_prometheus();
+        let bucket = |le: &str| {
+            format!("event_chains_duration_seconds_bucket{{event=\"Call\",le=\"{}\"}} ", le)
+        };
+        assert!(exposition.contains(&(bucket("0.001") + "0")));
+        assert!(exposition.contains(&(bucket("0.005") + "2")));
+        assert!(exposition.contains(&(bucket("0.01") + "3")));
+        assert!(exposition.contains(&(bucket("+Inf") + "3")));
+    }
+
+    #[test]
+    fn event_percentiles_come_from_recorded_durations() {
+        let clock = ManualClock::new();
+        let metrics = MetricsMiddleware::new().with_clock(clock.clone());
+        for micros in 1..=1000 {
+            call(&metrics, &clock, Duration::from_micros(micros), true);
+        }
+        // One slow outlier
+        call(&metrics, &clock, Duration::from_secs(1), false);
+
+        let stats = metrics.get_metrics("Call").unwrap();
+        assert_eq!(stats.total_executions, 1001);
+        assert!((500..=532).contains(&stats.p50_micros()));
+        assert!((900..=960).contains(&stats.p90_micros()));
+        assert!((990..=1024).contains(&stats.p99_micros()));
+        assert!((999..=1024).contains(&stats.p999_micros()));
+        assert_eq!(stats.percentile_micros(100.0), 1_000_000);
+        assert_eq!(stats.percentile_micros(0.0), 1);
+    }
+}
@@ -1,6 +1,18 @@
 /// Logging middleware for event execution
 pub mod logging;
 
+/// Output destinations (sinks) for the logging middleware
+pub mod log_sink;
+
+/// Masking of sensitive context values in log output
+pub mod redaction;
+
+/// Injectable time source for time-dependent middleware
+pub mod clock;
+
+/// Routing of middleware diagnostics to stdout, `log` or `tracing`
+pub(crate) mod diagnostics;
+
 /// Timing/performance measurement middleware
 pub mod timing;
 
@@ -13,11 +25,32 @@ pub mod metrics;
 /// Rate limiting middleware
 pub mod rate_limit;
 
+/// Concurrency-limiting bulkhead middleware
+pub mod bulkhead;
+
 /// Circuit breaker middleware for fault tolerance
 pub mod circuit_breaker;
 
 /// Chaos Middleware randomly injects failures to test system resilience
 pub mod chaos;
 
+/// Chaos experiments: steady-state hypotheses checked across injection phases
+pub mod chaos_experiment;
+
+/// Glob matching for event names and context keys
+pub(crate) mod pattern;
+
+/// Seedable random number generator for chaos and fuzzing
+pub(crate) mod rng;
+
 /// Fuzzing Middleware injects malicious/edge-case inputs to detect
 pub mod fuzzing;
+
+/// Payload corpora and typed hostile values for fuzzing
+pub mod fuzz_corpus;
+
+/// Mutation-based fuzz campaigns over whole chains
+pub mod fuzz_campaign;
+
+/// Oracles deciding which fuzzed executions are real findings
+pub mod fuzz_oracle;
@@ -0,0 +1,56 @@
+//! Simple glob matching for event names and context keys
+
+/// Match `text` against a glob `pattern` where `*` matches any run of
+/// characters (including none), e.g. `"Payment*"`, `"*Card"`, `"*user*"`
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+
+    for part in parts {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn literal_patterns_match_exactly() {
+        assert!(glob_match("ChargeCard", "ChargeCard"));
+        assert!(!glob_match("ChargeCard", "ChargeCards"));
+        assert!(!glob_match("", "a"));
+    }
+
+    #[test]
+    fn wildcards_match_any_run_of_characters() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("Payment*", "Payment"));
+        assert!(glob_match("Payment*", "PaymentRefund"));
+        assert!(glob_match("*Card", "ChargeCard"));
+        assert!(glob_match("*user*", "current_user_id"));
+        assert!(glob_match("a*b*c", "a-b-b-c"));
+        assert!(!glob_match("*Card", "CardHolder"));
+        assert!(!glob_match("a*b*c", "a-c-b"));
+    }
+
+    #[test]
+    fn prefix_and_suffix_do_not_overlap() {
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("ab*ba", "abba"));
+        assert!(!glob_match("a*a", "a"));
+    }
+}
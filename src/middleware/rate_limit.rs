@@ -2,6 +2,9 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -14,65 +17,224 @@ pub enum RateLimitStrategy {
     Wait,
 }
 
-/// Simple token bucket rate limiter
-#[derive(Clone)]
-pub struct RateLimiter {
-    tokens: Arc<Mutex<f64>>,
+struct BucketState {
+    tokens: f64,
     max_tokens: f64,
     refill_rate: f64, // tokens per second
-    last_refill: Arc<Mutex<Instant>>,
+    last_refill: Instant,
+    clock: SharedClock,
+}
+
+impl BucketState {
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+
+        if elapsed > 0.0 {
+            self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.max_tokens);
+            self.last_refill = now;
+        }
+    }
+
+    fn time_to_next_token(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+
+        Duration::try_from_secs_f64((1.0 - self.tokens) / self.refill_rate)
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+/// Token bucket rate limiter that can be shared between middleware instances
+///
+/// Cloning a `RateLimiter` produces a new handle to the **same** bucket, so
+/// several chains (or several threads) that call the same downstream service
+/// can draw from one quota.
+///
+/// The limiter reads time from a [`Clock`] (the system clock by default). The
+/// clock belongs to the shared bucket: [`set_clock`](Self::set_clock) swaps
+/// it for every handle.
+///
+/// # Example
+///
+/// ```ignore
+/// use event_chains::middleware::rate_limit::{RateLimiter, RateLimitMiddleware, RateLimitStrategy};
+///
+/// // 10 requests per second shared between two chains
+/// let limiter = RateLimiter::new(10.0, 10.0);
+///
+/// let orders = EventChain::new()
+///     .middleware(RateLimitMiddleware::with_limiter(limiter.clone(), RateLimitStrategy::Block))
+///     .event(CallPaymentApi);
+///
+/// let refunds = EventChain::new()
+///     .middleware(RateLimitMiddleware::with_limiter(limiter.clone(), RateLimitStrategy::Wait))
+///     .event(CallPaymentApi);
+///
+/// // Downstream returned 429: back off to 2 requests per second
+/// limiter.set_refill_rate(2.0);
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    state: Arc<Mutex<BucketState>>,
 }
 
 impl RateLimiter {
-    fn new(max_tokens: f64, refill_rate: f64) -> Self {
+    /// Create a new limiter with a full bucket
+    ///
+    /// # Arguments
+    ///
+    /// * `max_tokens` - Maximum number of tokens the bucket can hold (burst capacity)
+    /// * `refill_rate` - Tokens added per second
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tokens` is less than one or `refill_rate` is not a
+    /// positive, finite number: such a bucket never yields a token.
+    pub fn new(max_tokens: f64, refill_rate: f64) -> Self {
+        check_max_tokens(max_tokens);
+        check_refill_rate(refill_rate);
         Self {
-            tokens: Arc::new(Mutex::new(max_tokens)),
-            max_tokens,
-            refill_rate,
-            last_refill: Arc::new(Mutex::new(Instant::now())),
+            state: Arc::new(Mutex::new(BucketState {
+                tokens: max_tokens,
+                max_tokens,
+                refill_rate,
+                last_refill: Instant::now(),
+                clock: SystemClock::shared(),
+            })),
         }
     }
 
-    fn try_consume(&self, strategy: RateLimitStrategy) -> Result<(), Duration> {
-        loop {
-            self.refill();
+    /// Use `clock` instead of the system clock, e.g. a `ManualClock` in tests
+    ///
+    /// The clock is part of the shared bucket, so this affects every handle
+    /// cloned from this limiter, before or after this call. Refilling
+    /// restarts from the clock's current time.
+    pub fn set_clock<C: Clock + 'static>(&self, clock: C) {
+        let mut state = self.state.lock().unwrap();
+        state.clock = Arc::new(clock);
+        state.last_refill = state.clock.now();
+    }
+
+    /// Create a limiter allowing `requests_per_second` with an equal burst capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is zero.
+    pub fn per_second(requests_per_second: u32) -> Self {
+        let rps = requests_per_second as f64;
+        Self::new(rps, rps)
+    }
 
-            let mut tokens = self.tokens.lock().unwrap();
+    /// Try to take a token without waiting
+    ///
+    /// Returns the time until the next token becomes available if the bucket is empty.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.refilled();
 
-            if *tokens >= 1.0 {
-                *tokens -= 1.0;
-                return Ok(());
-            }
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(state.time_to_next_token())
+        }
+    }
 
-            match strategy {
-                RateLimitStrategy::Block => {
-                    let wait_time = Duration::from_secs_f64(1.0 / self.refill_rate);
-                    return Err(wait_time);
-                }
-                RateLimitStrategy::Wait => {
-                    drop(tokens);
-                    let wait_time = Duration::from_secs_f64(1.0 / self.refill_rate);
-                    std::thread::sleep(wait_time);
-                    continue;
-                }
-            }
+    /// Take a token, sleeping until one becomes available
+    pub fn acquire(&self) {
+        while let Err(wait_time) = self.try_acquire() {
+            // Sleep outside the lock so other handles can keep refilling
+            let clock = self.state.lock().unwrap().clock.clone();
+            clock.sleep(wait_time);
         }
     }
 
-    fn refill(&self) {
-        let mut last_refill = self.last_refill.lock().unwrap();
-        let now = Instant::now();
-        let elapsed = now.duration_since(*last_refill).as_secs_f64();
+    /// Number of tokens currently available (may be fractional)
+    pub fn available_tokens(&self) -> f64 {
+        let state = self.refilled();
+        state.tokens
+    }
 
-        if elapsed > 0.0 {
-            let mut tokens = self.tokens.lock().unwrap();
-            let new_tokens = elapsed * self.refill_rate;
-            *tokens = (*tokens + new_tokens).min(self.max_tokens);
-            *last_refill = now;
+    /// Time until at least one token is available (zero if one is available now)
+    pub fn time_to_next_token(&self) -> Duration {
+        let state = self.refilled();
+        state.time_to_next_token()
+    }
+
+    /// Get the maximum number of tokens (burst capacity)
+    pub fn max_tokens(&self) -> f64 {
+        self.state.lock().unwrap().max_tokens
+    }
+
+    /// Get the refill rate in tokens per second
+    pub fn refill_rate(&self) -> f64 {
+        self.state.lock().unwrap().refill_rate
+    }
+
+    /// Change the refill rate at runtime
+    ///
+    /// Tokens accrued at the old rate are credited before the change takes effect.
+    /// Affects every handle cloned from this limiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `refill_rate` is not a positive, finite number.
+    pub fn set_refill_rate(&self, refill_rate: f64) {
+        check_refill_rate(refill_rate);
+        let mut state = self.refilled();
+        state.refill_rate = refill_rate;
+    }
+
+    /// Lock the bucket and credit the tokens accrued since the last refill
+    fn refilled(&self) -> std::sync::MutexGuard<'_, BucketState> {
+        let mut state = self.state.lock().unwrap();
+        state.refill();
+        state
+    }
+
+    /// Change the burst capacity at runtime
+    ///
+    /// Available tokens are capped to the new capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tokens` is less than one.
+    pub fn set_max_tokens(&self, max_tokens: f64) {
+        check_max_tokens(max_tokens);
+        let mut state = self.refilled();
+        state.max_tokens = max_tokens;
+        state.tokens = state.tokens.min(max_tokens);
+    }
+
+    fn try_consume(&self, strategy: RateLimitStrategy) -> Result<(), Duration> {
+        match strategy {
+            RateLimitStrategy::Block => self.try_acquire(),
+            RateLimitStrategy::Wait => {
+                self.acquire();
+                Ok(())
+            }
         }
     }
 }
 
+fn check_refill_rate(refill_rate: f64) {
+    assert!(
+        refill_rate.is_finite() && refill_rate > 0.0,
+        "refill rate must be positive and finite, got {}",
+        refill_rate
+    );
+}
+
+fn check_max_tokens(max_tokens: f64) {
+    // NaN fails the comparison as well
+    assert!(
+        max_tokens >= 1.0,
+        "max tokens must be at least 1, got {}",
+        max_tokens
+    );
+}
+
 /// Middleware that enforces rate limiting on event execution
 ///
 /// # Middleware Failures
@@ -119,13 +281,12 @@ impl RateLimitMiddleware {
     ///
     /// * `requests_per_second` - Maximum number of events allowed per second
     /// * `strategy` - How to handle rate limit violations
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is zero.
     pub fn new(requests_per_second: u32, strategy: RateLimitStrategy) -> Self {
-        let rps = requests_per_second as f64;
-        Self {
-            limiter: RateLimiter::new(rps, rps),
-            strategy,
-            log_limits: true,
-        }
+        Self::with_limiter(RateLimiter::per_second(requests_per_second), strategy)
     }
 
     /// Create a rate limiter with custom burst capacity
@@ -135,18 +296,48 @@ impl RateLimitMiddleware {
     /// * `burst_capacity` - Maximum number of events that can be executed in a burst
     /// * `requests_per_second` - Rate at which capacity is refilled
     /// * `strategy` - How to handle rate limit violations
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst_capacity` or `requests_per_second` is zero.
     pub fn with_burst(
         burst_capacity: u32,
         requests_per_second: u32,
         strategy: RateLimitStrategy,
     ) -> Self {
+        Self::with_limiter(
+            RateLimiter::new(burst_capacity as f64, requests_per_second as f64),
+            strategy,
+        )
+    }
+
+    /// Create a rate limit middleware that draws from an existing (possibly shared) limiter
+    ///
+    /// # Arguments
+    ///
+    /// * `limiter` - Limiter handle; clones share the same token bucket
+    /// * `strategy` - How to handle rate limit violations
+    pub fn with_limiter(limiter: RateLimiter, strategy: RateLimitStrategy) -> Self {
         Self {
-            limiter: RateLimiter::new(burst_capacity as f64, requests_per_second as f64),
+            limiter,
             strategy,
             log_limits: true,
         }
     }
 
+    /// Get a handle to the limiter used by this middleware
+    pub fn limiter(&self) -> RateLimiter {
+        self.limiter.clone()
+    }
+
+    /// Use `clock` instead of the system clock
+    ///
+    /// Like [`RateLimiter::set_clock`], this affects every middleware and
+    /// handle sharing this middleware's limiter.
+    pub fn set_clock<C: Clock + 'static>(&self, clock: C) {
+        self.limiter.set_clock(clock);
+    }
+
     /// Configure whether to log rate limit violations
     pub fn with_logging(mut self, enabled: bool) -> Self {
         self.log_limits = enabled;
@@ -165,10 +356,15 @@ impl EventMiddleware for RateLimitMiddleware {
             Ok(()) => next(context),
             Err(wait_time) => {
                 if self.log_limits {
-                    println!(
-                        " Rate limit exceeded for {}. Try again in {:?}",
+                    emit(
+                        LogLevel::Warn,
+                        "rate_limit",
                         event.name(),
-                        wait_time
+                        &format!(
+                            "Rate limit exceeded for {}. Try again in {:?}",
+                            event.name(),
+                            wait_time
+                        ),
                     );
                 }
                 // Rate limiting is a policy/business rule, not infrastructure failure
@@ -178,3 +374,205 @@ impl EventMiddleware for RateLimitMiddleware {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::clock::ManualClock;
+
+    #[test]
+    #[should_panic(expected = "refill rate must be positive")]
+    fn new_rejects_zero_refill_rate() {
+        RateLimiter::new(10.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate must be positive")]
+    fn new_rejects_nan_refill_rate() {
+        RateLimiter::new(10.0, f64::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "max tokens must be at least 1")]
+    fn new_rejects_empty_bucket() {
+        RateLimiter::new(0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate must be positive")]
+    fn set_refill_rate_rejects_negative_rate() {
+        RateLimiter::new(10.0, 10.0).set_refill_rate(-1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate must be positive")]
+    fn set_refill_rate_rejects_infinite_rate() {
+        RateLimiter::new(10.0, 10.0).set_refill_rate(f64::INFINITY);
+    }
+
+    #[test]
+    fn set_clock_on_a_clone_is_seen_by_every_handle() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(1.0, 1.0);
+        let clone = limiter.clone();
+        clone.set_clock(clock.clone());
+
+        assert!(limiter.try_acquire().is_ok());
+        assert!(clone.try_acquire().is_err());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(clone.try_acquire().is_err());
+    }
+
+    #[test]
+    fn empty_bucket_reports_finite_wait() {
+        let limiter = RateLimiter::new(1.0, 0.5);
+        assert!(limiter.try_acquire().is_ok());
+
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn tokens_refill_over_time_up_to_capacity() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(2.0, 2.0);
+        limiter.set_clock(clock.clone());
+
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+        assert_eq!(limiter.try_acquire(), Err(Duration::from_millis(500)));
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(limiter.available_tokens(), 0.5);
+        assert_eq!(limiter.time_to_next_token(), Duration::from_millis(250));
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(limiter.available_tokens(), 2.0);
+    }
+
+    #[test]
+    fn acquire_sleeps_until_a_token_is_available() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(1.0, 4.0);
+        limiter.set_clock(clock.clone());
+
+        limiter.acquire();
+        limiter.acquire();
+        assert_eq!(clock.elapsed(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn refill_rate_change_credits_tokens_at_the_old_rate() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(10.0, 1.0);
+        limiter.set_clock(clock.clone());
+        for _ in 0..10 {
+            limiter.try_acquire().unwrap();
+        }
+
+        clock.advance(Duration::from_secs(2));
+        limiter.set_refill_rate(0.5);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(limiter.available_tokens(), 3.0);
+    }
+
+    #[test]
+    fn runtime_changes_are_seen_by_every_handle() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(4.0, 2.0);
+        limiter.set_clock(clock.clone());
+        let clone = limiter.clone();
+
+        clone.set_refill_rate(8.0);
+        clone.set_max_tokens(2.0);
+        assert_eq!(limiter.refill_rate(), 8.0);
+        assert_eq!(limiter.max_tokens(), 2.0);
+        assert_eq!(limiter.available_tokens(), 2.0);
+
+        limiter.try_acquire().unwrap();
+        limiter.try_acquire().unwrap();
+        assert_eq!(clone.available_tokens(), 0.0);
+        assert_eq!(clone.time_to_next_token(), Duration::from_millis(125));
+    }
+
+    #[test]
+    fn set_max_tokens_keeps_tokens_below_the_old_capacity() {
+        let limiter = RateLimiter::new(10.0, 1.0);
+        limiter.set_clock(ManualClock::new());
+        for _ in 0..7 {
+            limiter.try_acquire().unwrap();
+        }
+
+        limiter.set_max_tokens(20.0);
+        assert_eq!(limiter.available_tokens(), 3.0);
+    }
+
+    #[test]
+    fn threads_sharing_a_limiter_draw_from_one_bucket() {
+        let limiter = RateLimiter::new(100.0, 1.0);
+        limiter.set_clock(ManualClock::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                std::thread::spawn(move || {
+                    (0..50).filter(|_| limiter.try_acquire().is_ok()).count()
+                })
+            })
+            .collect();
+        let acquired: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        assert_eq!(acquired, 100);
+        assert_eq!(limiter.available_tokens(), 0.0);
+    }
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    #[test]
+    fn middleware_set_clock_applies_to_the_shared_limiter() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(1.0, 1.0);
+        let orders = RateLimitMiddleware::with_limiter(limiter.clone(), RateLimitStrategy::Block)
+            .with_logging(false);
+        let refunds = RateLimitMiddleware::with_limiter(limiter.clone(), RateLimitStrategy::Block)
+            .with_logging(false);
+        orders.set_clock(clock.clone());
+
+        let call = |middleware: &RateLimitMiddleware| {
+            middleware.execute(&Call, &mut EventContext::new(), &mut |_| EventResult::Success(()))
+        };
+        assert!(call(&orders).is_success());
+        assert!(!call(&refunds).is_success());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(call(&refunds).is_success());
+        assert!(!call(&orders).is_success());
+    }
+
+    #[test]
+    fn wait_strategy_delays_instead_of_rejecting() {
+        let clock = ManualClock::new();
+        let middleware = RateLimitMiddleware::with_burst(1, 2, RateLimitStrategy::Wait);
+        middleware.set_clock(clock.clone());
+
+        for _ in 0..3 {
+            let result = middleware.execute(&Call, &mut EventContext::new(), &mut |_| {
+                EventResult::Success(())
+            });
+            assert!(result.is_success());
+        }
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::core::event_context::EventContext;
+use crate::middleware::pattern::glob_match;
+use std::any::Any;
+
+/// Default replacement text for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Rules for masking sensitive context values before they are logged
+///
+/// Keys and patterns are split into words at `_`, `-`, `.`, `:` and camelCase
+/// boundaries, ignoring case. A key is sensitive if the words of a pattern
+/// appear consecutively in it, so `"password"` also masks `"db_password"`
+/// and `"PasswordHash"`, and `"api_key"` masks `"stripeApiKey"`, but
+/// `"token"` does not mask `"tokenizer_config"`. Patterns containing `*` are
+/// globs matched against the whole key instead, e.g. `"*token*"`.
+///
+/// The same `Redactor` can be shared by
+/// [`LoggingMiddleware`](crate::middleware::logging::LoggingMiddleware) and
+/// [`FuzzingMiddleware`](crate::middleware::fuzzing::FuzzingMiddleware), so
+/// payloads injected into sensitive keys never show up in the logs either.
+///
+/// # Example
+///
+/// ```ignore
+/// use event_chains::middleware::redaction::Redactor;
+///
+/// let redactor = Redactor::new().with_keys(["password", "token"]);
+///
+/// let chain = EventChain::new()
+///     .middleware(
+///         LoggingMiddleware::info()
+///             .with_context_keys(["request_id", "user_id", "password"])
+///             .with_redaction(redactor.clone()),
+///     )
+///     .event(LoginEvent);
+///
+/// // [INFO] Completed event: Login request_id=42 user_id=alice password=[REDACTED]
+/// ```
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<KeyPattern>,
+    mask: String,
+}
+
+/// A sensitive key pattern, see [`Redactor`]
+#[derive(Debug, Clone)]
+enum KeyPattern {
+    /// Consecutive lowercase words
+    Words(Vec<String>),
+    /// Lowercase glob matched against the whole key
+    Glob(String),
+}
+
+impl KeyPattern {
+    fn new(pattern: &str) -> Self {
+        if pattern.contains('*') {
+            KeyPattern::Glob(pattern.to_lowercase())
+        } else {
+            KeyPattern::Words(words(pattern))
+        }
+    }
+
+    fn matches(&self, key: &str, key_words: &[String]) -> bool {
+        match self {
+            KeyPattern::Words(words) => {
+                !words.is_empty() && key_words.windows(words.len()).any(|run| run == words)
+            }
+            KeyPattern::Glob(glob) => glob_match(glob, &key.to_lowercase()),
+        }
+    }
+}
+
+/// Split a key into lowercase words at separators and camelCase boundaries
+fn words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1);
+        // "dbPassword" -> db|Password, "APIKey" -> API|Key
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_numeric()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            });
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+impl Redactor {
+    /// Create a redactor with no sensitive keys
+    pub fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+            mask: REDACTED.to_string(),
+        }
+    }
+
+    /// Create a redactor for common secret names (password, secret, token, api_key, authorization)
+    pub fn common() -> Self {
+        Self::new().with_keys(["password", "secret", "token", "api_key", "authorization"])
+    }
+
+    /// Mark keys containing the words of `pattern` (or matching it, if it is a glob) as sensitive
+    pub fn with_key(mut self, pattern: &str) -> Self {
+        self.patterns.push(KeyPattern::new(pattern));
+        self
+    }
+
+    /// Mark keys matching any of `patterns` as sensitive (see [`with_key`](Self::with_key))
+    pub fn with_keys<'a>(mut self, patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        self.patterns.extend(patterns.into_iter().map(KeyPattern::new));
+        self
+    }
+
+    /// Set the replacement text (default: `[REDACTED]`)
+    pub fn with_mask(mut self, mask: &str) -> Self {
+        self.mask = mask.to_string();
+        self
+    }
+
+    /// Whether values stored under `key` must be masked
+    pub fn is_sensitive(&self, key: &str) -> bool {
+        let key_words = words(key);
+        self.patterns.iter().any(|pattern| pattern.matches(key, &key_words))
+    }
+
+    /// Return `value`, or the mask if `key` is sensitive
+    pub fn redact(&self, key: &str, value: &str) -> String {
+        if self.is_sensitive(key) {
+            self.mask.clone()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Render the context value under `key` for logging, masking it if sensitive
+    ///
+    /// Returns `None` if the key is missing. Values of types other than
+    /// strings, integers, floats and booleans are rendered as `<opaque>`.
+    pub fn render(&self, context: &EventContext, key: &str) -> Option<String> {
+        let value = context.get_raw(key)?;
+        if self.is_sensitive(key) {
+            return Some(self.mask.clone());
+        }
+        Some(render_value(value))
+    }
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn render_value(value: &(dyn Any + Send + Sync)) -> String {
+    macro_rules! try_display {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(v) = value.downcast_ref::<$ty>() {
+                    return v.to_string();
+                }
+            )*
+        };
+    }
+
+    try_display!(String, &'static str, bool, char);
+    try_display!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+    "<opaque>".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_result::EventResult;
+    use crate::events::chainable_event::ChainableEvent;
+    use crate::events::event_middleware::EventMiddleware;
+    use crate::middleware::log_sink::MemorySink;
+    use crate::middleware::logging::LoggingMiddleware;
+
+    #[test]
+    fn patterns_match_whole_words_in_any_case_style() {
+        let redactor = Redactor::new().with_keys(["password", "api_key"]);
+
+        for key in ["password", "db_password", "PasswordHash", "user.password", "API_KEY"] {
+            assert!(redactor.is_sensitive(key), "{} should be sensitive", key);
+        }
+        for key in ["stripeApiKey", "APIKey", "x-api-key"] {
+            assert!(redactor.is_sensitive(key), "{} should be sensitive", key);
+        }
+        for key in ["passwords_reset_count", "api", "key_id", "apikey_hint"] {
+            assert!(!redactor.is_sensitive(key), "{} should not be sensitive", key);
+        }
+    }
+
+    #[test]
+    fn common_secrets_do_not_mask_lookalike_keys() {
+        let redactor = Redactor::common();
+
+        assert!(redactor.is_sensitive("refresh_token"));
+        assert!(redactor.is_sensitive("clientSecret"));
+        assert!(!redactor.is_sensitive("tokenizer_config"));
+        assert!(!redactor.is_sensitive("secretary_name"));
+    }
+
+    #[test]
+    fn glob_patterns_match_the_whole_key() {
+        let redactor = Redactor::new().with_key("*token*").with_mask("***");
+
+        assert!(redactor.is_sensitive("tokenizer_config"));
+        assert!(redactor.is_sensitive("CSRFToken"));
+        assert_eq!(redactor.redact("tokenizer_config", "bert"), "***");
+        assert_eq!(redactor.redact("user_id", "alice"), "alice");
+    }
+
+    #[test]
+    fn render_masks_sensitive_values_and_formats_the_rest() {
+        let redactor = Redactor::new().with_key("password");
+        let mut context = EventContext::new();
+        context.set("password", "hunter2".to_string());
+        context.set("attempts", 3u32);
+        context.set("session", vec![1u8]);
+
+        assert_eq!(redactor.render(&context, "password").as_deref(), Some(REDACTED));
+        assert_eq!(redactor.render(&context, "attempts").as_deref(), Some("3"));
+        assert_eq!(redactor.render(&context, "session").as_deref(), Some("<opaque>"));
+        assert_eq!(redactor.render(&context, "missing"), None);
+    }
+
+    struct Login;
+
+    impl ChainableEvent for Login {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Login"
+        }
+    }
+
+    #[test]
+    fn logging_captures_context_keys_with_redaction() {
+        let sink = MemorySink::new();
+        let logging = LoggingMiddleware::info()
+            .with_sink(sink.clone())
+            .with_context_keys(["request_id", "user_id", "password", "missing"])
+            .with_redaction(Redactor::new().with_key("password"));
+        let mut context = EventContext::new();
+        context.set("request_id", 42u64);
+        context.set("user_id", "alice".to_string());
+        context.set("password", "hunter2".to_string());
+
+        logging.execute(&Login, &mut context, &mut |_| EventResult::Success(()));
+
+        let records = sink.records();
+        assert_eq!(
+            records[0].to_text(),
+            "[INFO] Completed event: Login request_id=42 user_id=alice password=[REDACTED]"
+        );
+    }
+}
@@ -2,6 +2,10 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Backoff strategy for retry attempts
@@ -64,6 +68,7 @@ pub struct RetryMiddleware {
     max_retries: usize,
     backoff: BackoffStrategy,
     log_retries: bool,
+    clock: SharedClock,
 }
 
 impl RetryMiddleware {
@@ -73,6 +78,7 @@ impl RetryMiddleware {
             max_retries,
             backoff: BackoffStrategy::None,
             log_retries: true,
+            clock: SystemClock::shared(),
         }
     }
 
@@ -88,6 +94,15 @@ impl RetryMiddleware {
         self
     }
 
+    /// Sleep between attempts on `clock` instead of the system clock
+    ///
+    /// With a `ManualClock`, backoff delays advance the clock instead of
+    /// blocking, so retry tests run instantly.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Create retry middleware with exponential backoff
     pub fn exponential(max_retries: usize, initial: Duration, max: Duration) -> Self {
         Self::new(max_retries).with_backoff(BackoffStrategy::Exponential { initial, max })
@@ -130,10 +145,11 @@ impl EventMiddleware for RetryMiddleware {
             match &result {
                 EventResult::Success(_) => {
                     if attempts > 1 && self.log_retries {
-                        println!(
-                            " {} succeeded after {} attempts",
+                        emit(
+                            LogLevel::Info,
+                            "retry",
                             event.name(),
-                            attempts
+                            &format!("{} succeeded after {} attempts", event.name(), attempts),
                         );
                     }
                     return result;
@@ -142,9 +158,14 @@ impl EventMiddleware for RetryMiddleware {
                     // DO NOT retry middleware failures - they indicate infrastructure problems
                     // Pass them through immediately
                     if self.log_retries {
-                        println!(
-                            " {} middleware failure - not retrying (infrastructure issue)",
-                            event.name()
+                        emit(
+                            LogLevel::Warn,
+                            "retry",
+                            event.name(),
+                            &format!(
+                                "{} middleware failure - not retrying (infrastructure issue)",
+                                event.name()
+                            ),
                         );
                     }
                     return result;
@@ -152,11 +173,16 @@ impl EventMiddleware for RetryMiddleware {
                 EventResult::Failure(err) => {
                     if attempts >= self.max_retries {
                         if self.log_retries {
-                            println!(
-                                " {} failed after {} attempts: {}",
+                            emit(
+                                LogLevel::Error,
+                                "retry",
                                 event.name(),
-                                attempts,
-                                err
+                                &format!(
+                                    "{} failed after {} attempts: {}",
+                                    event.name(),
+                                    attempts,
+                                    err
+                                ),
                             );
                         }
                         return result;
@@ -166,28 +192,110 @@ impl EventMiddleware for RetryMiddleware {
 
                     if self.log_retries {
                         if delay.is_zero() {
-                            println!(
-                                " {} attempt {}/{} failed, retrying immediately...",
+                            emit(
+                                LogLevel::Warn,
+                                "retry",
                                 event.name(),
-                                attempts,
-                                self.max_retries
+                                &format!(
+                                    "{} attempt {}/{} failed, retrying immediately...",
+                                    event.name(),
+                                    attempts,
+                                    self.max_retries
+                                ),
                             );
                         } else {
-                            println!(
-                                " {} attempt {}/{} failed, retrying in {:?}...",
+                            emit(
+                                LogLevel::Warn,
+                                "retry",
                                 event.name(),
-                                attempts,
-                                self.max_retries,
-                                delay
+                                &format!(
+                                    "{} attempt {}/{} failed, retrying in {:?}...",
+                                    event.name(),
+                                    attempts,
+                                    self.max_retries,
+                                    delay
+                                ),
                             );
                         }
                     }
 
                     if !delay.is_zero() {
-                        std::thread::sleep(delay);
+                        self.clock.sleep(delay);
                     }
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::clock::ManualClock;
+
+    struct Call;
+
+    impl ChainableEvent for Call {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Call"
+        }
+    }
+
+    /// Run `retry` against an always-failing event, returning the attempts and time slept
+    fn always_fail(retry: RetryMiddleware, clock: &ManualClock) -> (usize, Duration) {
+        let mut attempts = 0;
+        let result = retry.with_logging(false).with_clock(clock.clone()).execute(
+            &Call,
+            &mut EventContext::new(),
+            &mut |_| {
+                attempts += 1;
+                EventResult::Failure("down".to_string())
+            },
+        );
+        assert!(!result.is_success());
+        (attempts, clock.elapsed())
+    }
+
+    #[test]
+    fn exponential_backoff_doubles_up_to_the_cap() {
+        let clock = ManualClock::new();
+        let retry =
+            RetryMiddleware::exponential(5, Duration::from_millis(100), Duration::from_millis(300));
+
+        // 100 + 200 + 300 + 300
+        assert_eq!(always_fail(retry, &clock), (5, Duration::from_millis(900)));
+    }
+
+    #[test]
+    fn linear_backoff_grows_by_the_increment() {
+        let clock = ManualClock::new();
+        let retry = RetryMiddleware::new(4).with_backoff(BackoffStrategy::Linear {
+            initial: Duration::from_millis(10),
+            increment: Duration::from_millis(5),
+        });
+
+        // 10 + 15 + 20
+        assert_eq!(always_fail(retry, &clock), (4, Duration::from_millis(45)));
+    }
+
+    #[test]
+    fn middleware_failures_are_not_retried() {
+        let clock = ManualClock::new();
+        let mut attempts = 0;
+        let result = RetryMiddleware::fixed(3, Duration::from_secs(1))
+            .with_logging(false)
+            .with_clock(clock.clone())
+            .execute(&Call, &mut EventContext::new(), &mut |_| {
+                attempts += 1;
+                EventResult::MiddlewareFailure("disk full".to_string())
+            });
+
+        assert!(matches!(result, EventResult::MiddlewareFailure(_)));
+        assert_eq!(attempts, 1);
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,49 @@
+//! Small seedable PRNG used by the adversarial middleware
+//!
+//! Chaos and fuzzing runs must be replayable: given the same seed and the
+//! same sequence of calls, they make exactly the same decisions. SplitMix64
+//! is tiny, fast and good enough for picking faults; it is NOT suitable for
+//! anything security-related.
+
+/// SplitMix64 pseudo-random number generator
+#[derive(Debug, Clone)]
+pub(crate) struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `[0.0, 1.0)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform index in `0..len` (`len` must be non-zero)
+    pub(crate) fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+/// Pick a fresh seed from the current time, for runs without an explicit seed
+pub(crate) fn random_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    RandomState::new().hash_one(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos(),
+    )
+}
@@ -2,15 +2,114 @@ use crate::core::event_context::EventContext;
 use crate::core::event_result::EventResult;
 use crate::events::chainable_event::ChainableEvent;
 use crate::events::event_middleware::EventMiddleware;
-use std::time::{Duration, Instant};
+use crate::middleware::clock::{Clock, SharedClock, SystemClock};
+use crate::middleware::diagnostics::emit;
+use crate::middleware::logging::LogLevel;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Context key of the [`TimingBreakdown`] accumulated by [`TimingMiddleware::with_breakdown`]
+pub const TIMING_BREAKDOWN_KEY: &str = "timing:breakdown";
+
+/// Duration statistics for one event, accumulated across chain executions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingStats {
+    pub count: u64,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub last: Duration,
+    /// Number of executions that exceeded the time budget
+    pub over_budget: u64,
+}
+
+impl TimingStats {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            total: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            last: Duration::ZERO,
+            over_budget: 0,
+        }
+    }
+
+    fn record(&mut self, duration: Duration, over_budget: bool) {
+        self.count += 1;
+        self.total += duration;
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+        self.last = duration;
+        if over_budget {
+            self.over_budget += 1;
+        }
+    }
+
+    /// Average duration
+    pub fn average(&self) -> Duration {
+        self.total
+            .checked_div(self.count.min(u32::MAX as u64) as u32)
+            .unwrap_or_default()
+    }
+}
+
+/// Per-event durations of one chain execution, in execution order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimingBreakdown {
+    pub entries: Vec<(String, Duration)>,
+    /// Chain execution the entries belong to
+    run: u64,
+}
+
+impl TimingBreakdown {
+    /// Sum of all recorded event durations
+    pub fn total(&self) -> Duration {
+        self.entries.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// The slowest event, if any
+    pub fn slowest(&self) -> Option<(&str, Duration)> {
+        self.entries
+            .iter()
+            .max_by_key(|(_, duration)| *duration)
+            .map(|(name, duration)| (name.as_str(), *duration))
+    }
+
+    /// Share of the total time spent in each event, in percent
+    pub fn percentages(&self) -> Vec<(&str, f64)> {
+        let total = self.total().as_secs_f64();
+        self.entries
+            .iter()
+            .map(|(name, duration)| {
+                let percent = if total > 0.0 {
+                    duration.as_secs_f64() / total * 100.0
+                } else {
+                    0.0
+                };
+                (name.as_str(), percent)
+            })
+            .collect()
+    }
+}
 
 /// Middleware that measures and logs event execution time
 ///
+/// Durations are accumulated per event name across executions; see
+/// [`get_stats`](Self::get_stats). Clones share the same statistics.
+///
 /// # Middleware Failures
 ///
 /// This middleware does not produce infrastructure failures - timing measurement
 /// is always available and never returns `MiddlewareFailure`.
 ///
+/// # Time Budgets
+///
+/// With [`with_budget`](Self::with_budget), an event that takes longer than the
+/// budget still succeeds, but a warning is recorded and reported in
+/// `ChainResult::warnings` (the chain completes with `CompletedWithWarnings`).
+///
 /// # Example
 ///
 /// ```ignore
@@ -26,10 +125,30 @@ use std::time::{Duration, Instant};
 /// let chain = EventChain::new()
 ///     .middleware(TimingMiddleware::new().with_threshold(Duration::from_millis(100)))
 ///     .event(MyEvent);
+///
+/// // Warn about events over 50ms and keep a per-run breakdown
+/// let timing = TimingMiddleware::new()
+///     .with_budget(Duration::from_millis(50))
+///     .with_breakdown();
+/// let chain = EventChain::new()
+///     .middleware(timing.clone())
+///     .event(MyEvent);
+///
+/// let result = chain.execute(&mut context);
+/// for warning in &result.warnings {
+///     println!("{}: {}", warning.event_name, warning.error_message);
+/// }
+/// let breakdown: TimingBreakdown = context.get(TIMING_BREAKDOWN_KEY).unwrap();
+/// timing.print_stats();
 /// ```
+#[derive(Clone)]
 pub struct TimingMiddleware {
     threshold: Option<Duration>,
+    budget: Option<Duration>,
     store_in_context: bool,
+    store_breakdown: bool,
+    stats: Arc<Mutex<HashMap<String, TimingStats>>>,
+    clock: SharedClock,
 }
 
 impl TimingMiddleware {
@@ -37,7 +156,11 @@ impl TimingMiddleware {
     pub fn new() -> Self {
         Self {
             threshold: None,
+            budget: None,
             store_in_context: false,
+            store_breakdown: false,
+            stats: Arc::new(Mutex::new(HashMap::new())),
+            clock: SystemClock::shared(),
         }
     }
 
@@ -55,6 +178,75 @@ impl TimingMiddleware {
         self
     }
 
+    /// Record a warning for events that take longer than `budget`
+    ///
+    /// Over-budget events are logged at warning level regardless of the
+    /// logging threshold.
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Accumulate the duration of every event of a chain execution in a
+    /// [`TimingBreakdown`] stored under [`TIMING_BREAKDOWN_KEY`]
+    ///
+    /// A reused context only holds the events of the latest chain execution.
+    /// Events of chains run by an event on the same context are included.
+    pub fn with_breakdown(mut self) -> Self {
+        self.store_breakdown = true;
+        self
+    }
+
+    /// Measure durations with `clock` instead of the system clock
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get statistics for a specific event
+    pub fn get_stats(&self, event_name: &str) -> Option<TimingStats> {
+        self.stats.lock().ok()?.get(event_name).copied()
+    }
+
+    /// Get statistics for all events
+    pub fn get_all_stats(&self) -> HashMap<String, TimingStats> {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Reset all statistics
+    pub fn reset_stats(&self) {
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.clear();
+        }
+    }
+
+    /// Print a summary of all statistics
+    pub fn print_stats(&self) {
+        let stats = self.get_all_stats();
+        let mut names: Vec<_> = stats.keys().collect();
+        names.sort();
+
+        println!("\n=== Event Timing Summary ===");
+        println!("{:<25} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                 "Event", "Count", "Avg", "Min", "Max", "Last", "Over Budget");
+        println!("{}", "-".repeat(99));
+
+        for name in names {
+            let s = &stats[name];
+            println!(
+                "{:<25} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                name,
+                s.count,
+                Self::format_duration(s.average()),
+                Self::format_duration(s.min),
+                Self::format_duration(s.max),
+                Self::format_duration(s.last),
+                s.over_budget
+            );
+        }
+        println!();
+    }
+
     fn should_log(&self, duration: Duration) -> bool {
         match self.threshold {
             Some(threshold) => duration >= threshold,
@@ -81,15 +273,33 @@ impl EventMiddleware for TimingMiddleware {
         context: &mut EventContext,
         next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
     ) -> EventResult<()> {
-        let start = Instant::now();
+        let start = self.clock.now();
         let result = next(context);
-        let duration = start.elapsed();
+        let duration = self.clock.now().duration_since(start);
+        let over_budget = self.budget.is_some_and(|budget| duration > budget);
 
-        if self.should_log(duration) {
-            println!(
-                "  {} took {}",
+        if let Ok(mut stats) = self.stats.lock() {
+            stats
+                .entry(event.name().to_string())
+                .or_insert_with(TimingStats::new)
+                .record(duration, over_budget);
+        }
+
+        if over_budget {
+            let message = format!(
+                "{} exceeded time budget: took {} (budget {})",
+                event.name(),
+                Self::format_duration(duration),
+                Self::format_duration(self.budget.unwrap_or_default())
+            );
+            emit(LogLevel::Warn, "timing", event.name(), &message);
+            context.add_warning(event.name(), message);
+        } else if self.should_log(duration) {
+            emit(
+                LogLevel::Info,
+                "timing",
                 event.name(),
-                Self::format_duration(duration)
+                &format!("{} took {}", event.name(), Self::format_duration(duration)),
             );
         }
 
@@ -98,6 +308,20 @@ impl EventMiddleware for TimingMiddleware {
             context.set(&key, duration.as_micros() as u64);
         }
 
+        if self.store_breakdown {
+            // Entries of an earlier execution with the same context are discarded
+            let run = context.run_id();
+            let mut breakdown = context
+                .get::<TimingBreakdown>(TIMING_BREAKDOWN_KEY)
+                .filter(|breakdown| breakdown.run == run)
+                .unwrap_or(TimingBreakdown {
+                    entries: Vec::new(),
+                    run,
+                });
+            breakdown.entries.push((event.name().to_string(), duration));
+            context.set(TIMING_BREAKDOWN_KEY, breakdown);
+        }
+
         result
     }
 }
@@ -107,3 +331,77 @@ impl Default for TimingMiddleware {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_chain::EventChain;
+    use crate::middleware::clock::ManualClock;
+
+    struct Sleep(Duration, ManualClock);
+
+    impl ChainableEvent for Sleep {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            self.1.advance(self.0);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Sleep"
+        }
+    }
+
+    #[test]
+    fn breakdown_is_reset_per_chain_execution() {
+        let clock = ManualClock::new();
+        let chain = EventChain::new()
+            .middleware(TimingMiddleware::new().with_breakdown().with_clock(clock.clone()))
+            .event(Sleep(Duration::from_millis(5), clock.clone()))
+            .event(Sleep(Duration::from_millis(10), clock));
+        let mut context = EventContext::new();
+
+        chain.execute(&mut context);
+        chain.execute(&mut context);
+
+        let breakdown: TimingBreakdown = context.get(TIMING_BREAKDOWN_KEY).unwrap();
+        assert_eq!(breakdown.entries.len(), 2);
+        assert_eq!(breakdown.total(), Duration::from_millis(15));
+        assert_eq!(breakdown.slowest(), Some(("Sleep", Duration::from_millis(10))));
+    }
+
+    /// Runs `chain` on the context it is given
+    struct Nested(EventChain);
+
+    impl ChainableEvent for Nested {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            self.0.execute(context);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Nested"
+        }
+    }
+
+    #[test]
+    fn nested_chain_keeps_the_outer_breakdown() {
+        let clock = ManualClock::new();
+        let timing = || TimingMiddleware::new().with_breakdown().with_clock(clock.clone());
+        let inner = EventChain::new()
+            .middleware(timing())
+            .event(Sleep(Duration::from_millis(10), clock.clone()));
+        let outer = EventChain::new()
+            .middleware(timing())
+            .event(Sleep(Duration::from_millis(5), clock.clone()))
+            .event(Nested(inner));
+        let mut context = EventContext::new();
+
+        outer.execute(&mut context);
+        outer.execute(&mut context);
+
+        let breakdown: TimingBreakdown = context.get(TIMING_BREAKDOWN_KEY).unwrap();
+        let names: Vec<&str> = breakdown.entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Sleep", "Sleep", "Nested"]);
+        assert_eq!(breakdown.entries[2].1, Duration::from_millis(10));
+    }
+}